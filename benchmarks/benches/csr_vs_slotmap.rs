@@ -0,0 +1,43 @@
+use benchmarks::random_graph;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use noodlez::prelude::*;
+
+fn sum_neighbors<N: Copy + std::iter::Sum<N>, E, G: GraphRead<N, E>>(graph: &G) -> N {
+    graph
+        .nodes()
+        .flat_map(|id| graph.outcoming_nodes(id))
+        .map(|id| *graph.node(id).unwrap())
+        .sum()
+}
+
+fn sum_edges<N, E: Copy + std::iter::Sum<E>, G: GraphRead<N, E>>(graph: &G) -> E {
+    graph
+        .nodes()
+        .flat_map(|id| graph.outcoming_edges(id))
+        .map(|id| *graph.edge(id).unwrap())
+        .sum()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let graph = random_graph(10_000, 100_000);
+    let frozen = graph.freeze();
+
+    c.bench_function("slotmap graph neighbor sweep", |b| {
+        b.iter(|| sum_neighbors(black_box(&graph)))
+    });
+    c.bench_function("csr graph neighbor sweep", |b| {
+        b.iter(|| sum_neighbors(black_box(&frozen)))
+    });
+
+    c.bench_function("slotmap graph edge sweep", |b| {
+        b.iter(|| sum_edges(black_box(&graph)))
+    });
+    c.bench_function("csr graph edge sweep", |b| {
+        b.iter(|| sum_edges(black_box(&frozen)))
+    });
+
+    c.bench_function("freeze graph", |b| b.iter(|| black_box(&graph).freeze()));
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
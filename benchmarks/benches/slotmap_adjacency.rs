@@ -0,0 +1,93 @@
+use benchmarks::random_graph;
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use noodlez::prelude::*;
+use noodlez::storage::{
+    edge::{EdgeEnds, EdgeId},
+    node::NodeId,
+};
+
+// the route neighbor edges took before the adjacency lists held edge ids: every distinct
+// neighbor is resolved to its edges through the edge ends lookup
+fn neighbor_edges_through_ends(
+    graph: &Graph<usize, usize>,
+    id: NodeId<Graph<usize, usize>>,
+) -> impl Iterator<Item = EdgeId<Graph<usize, usize>>> + '_ {
+    let distinct = |nodes: Vec<_>| {
+        (0..nodes.len())
+            .filter(|i| !nodes[..*i].contains(&nodes[*i]))
+            .map(|i| nodes[i])
+            .collect::<Vec<_>>()
+    };
+    let incoming = distinct(graph.incoming_nodes(id).collect())
+        .into_iter()
+        .flat_map(move |from| graph.edges_between(EdgeEnds::new(from, id)));
+    let outgoing = distinct(graph.outcoming_nodes(id).collect())
+        .into_iter()
+        .flat_map(move |to| graph.edges_between(EdgeEnds::new(id, to)));
+    incoming.chain(outgoing)
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let graph = random_graph(10_000, 100_000);
+
+    c.bench_function("slotmap graph incoming node sweep", |b| {
+        b.iter(|| {
+            let graph = black_box(&graph);
+            graph
+                .nodes()
+                .flat_map(|id| graph.incoming_nodes(id))
+                .map(|id| graph[id])
+                .sum::<usize>()
+        })
+    });
+    c.bench_function("slotmap graph neighbor edge sweep", |b| {
+        b.iter(|| {
+            let graph = black_box(&graph);
+            graph
+                .nodes()
+                .flat_map(|id| graph.neighbor_edges(id))
+                .map(|id| graph[id])
+                .sum::<usize>()
+        })
+    });
+    c.bench_function(
+        "slotmap graph neighbor edge sweep through edge ends (before)",
+        |b| {
+            b.iter(|| {
+                let graph = black_box(&graph);
+                graph
+                    .nodes()
+                    .flat_map(|id| neighbor_edges_through_ends(graph, id))
+                    .map(|id| graph[id])
+                    .sum::<usize>()
+            })
+        },
+    );
+    c.bench_function("slotmap graph remove 10000 edges", |b| {
+        b.iter_batched(
+            || (graph.clone(), graph.edges().step_by(10).collect::<Vec<_>>()),
+            |(mut graph, edges)| {
+                edges.into_iter().for_each(|id| {
+                    graph.remove_edge(id);
+                });
+                graph
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    c.bench_function("slotmap graph remove 1000 nodes", |b| {
+        b.iter_batched(
+            || (graph.clone(), graph.nodes().step_by(10).collect::<Vec<_>>()),
+            |(mut graph, nodes)| {
+                nodes.into_iter().for_each(|id| {
+                    graph.remove_node(id);
+                });
+                graph
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+use noodlez::prelude::*;
+
+/// Multigraph with deterministic pseudo random edges, so that benches are comparable across runs
+/// and storages. Edge weights count up from zero.
+pub fn random_graph(nodes: usize, edges: usize) -> Graph<usize, usize> {
+    let mut graph = Graph::multigraph();
+    let ids = (0..nodes).map(|i| graph.add_node(i)).collect::<Vec<_>>();
+
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as usize % nodes
+    };
+    (0..edges).for_each(|i| {
+        graph.add_edge(ids[next()], ids[next()], i);
+    });
+
+    graph
+}
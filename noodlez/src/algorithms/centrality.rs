@@ -0,0 +1,335 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+
+use crate::storage::{node::NodeId, traits::GraphRead};
+
+use super::{arcs, dense_indices};
+
+/// Scores of an iterative centrality measure together with how the iteration went
+pub struct IterativeScores<G> {
+    scores: HashMap<NodeId<G>, f64>,
+    iterations: usize,
+    converged: bool,
+}
+
+impl<G> IterativeScores<G> {
+    #[inline]
+    pub fn score(&self, node: NodeId<G>) -> Option<f64> {
+        self.scores.get(&node).copied()
+    }
+
+    #[inline]
+    pub fn scores(&self) -> &HashMap<NodeId<G>, f64> {
+        &self.scores
+    }
+
+    #[inline]
+    pub fn into_scores(self) -> HashMap<NodeId<G>, f64> {
+        self.scores
+    }
+
+    /// Number of iterations which were run
+    #[inline]
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Whether the scores changed less than the tolerance before the iteration cap was reached
+    #[inline]
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+impl<G> Clone for IterativeScores<G> {
+    fn clone(&self) -> Self {
+        Self {
+            scores: self.scores.clone(),
+            iterations: self.iterations,
+            converged: self.converged,
+        }
+    }
+}
+
+impl<G> Debug for IterativeScores<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IterativeScores")
+            .field("scores", &self.scores)
+            .field("iterations", &self.iterations)
+            .field("converged", &self.converged)
+            .finish()
+    }
+}
+
+// nodes by dense index and arcs between those indices
+fn indexed_arcs<G, N, E>(graph: &G) -> (Vec<NodeId<G>>, Vec<(usize, usize)>)
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, indices) = dense_indices(graph);
+    let arcs = arcs(graph, |_, _| ())
+        .into_iter()
+        .map(|(from, to, _, _)| (indices[&from], indices[&to]))
+        .collect();
+    (nodes, arcs)
+}
+
+// runs `step` until the summed absolute change of the scores drops below the tolerance
+fn iterate<G>(
+    nodes: Vec<NodeId<G>>,
+    mut scores: Vec<f64>,
+    tolerance: f64,
+    max_iterations: usize,
+    mut step: impl FnMut(&[f64]) -> Vec<f64>,
+) -> IterativeScores<G> {
+    let mut iterations = 0;
+    let mut converged = false;
+    while iterations < max_iterations && !converged {
+        let next = step(&scores);
+        let change = next
+            .iter()
+            .zip(&scores)
+            .map(|(next, previous)| (next - previous).abs())
+            .sum::<f64>();
+        scores = next;
+        iterations += 1;
+        converged = change < tolerance;
+    }
+
+    IterativeScores {
+        scores: nodes.into_iter().zip(scores).collect(),
+        iterations,
+        converged,
+    }
+}
+
+/// Ranks nodes by the chance to end up on them during a random walk along the edges, which jumps
+/// to a random node with probability `1 - damping` or when it's stuck. The jump targets are picked
+/// with the weights of the personalization, which defaults to uniform for missing or all zero
+/// weights. Scores sum up to one.
+pub fn page_rank<G, N, E>(
+    graph: &G,
+    damping: f64,
+    personalization: Option<&HashMap<NodeId<G>, f64>>,
+    tolerance: f64,
+    max_iterations: usize,
+) -> IterativeScores<G>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, arcs) = indexed_arcs(graph);
+    let count = nodes.len();
+
+    let mut jumps = nodes
+        .iter()
+        .map(|node| {
+            personalization
+                .and_then(|weights| weights.get(node))
+                .copied()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let total = jumps.iter().sum::<f64>();
+    if total > 0.0 {
+        jumps.iter_mut().for_each(|jump| *jump /= total);
+    } else {
+        jumps.fill(1.0 / count as f64);
+    }
+
+    let mut out_degrees = vec![0usize; count];
+    arcs.iter().for_each(|(from, _)| out_degrees[*from] += 1);
+
+    iterate(nodes, jumps.clone(), tolerance, max_iterations, |ranks| {
+        let dangling = out_degrees
+            .iter()
+            .zip(ranks)
+            .filter(|(degree, _)| **degree == 0)
+            .map(|(_, rank)| rank)
+            .sum::<f64>();
+        let mut next = jumps
+            .iter()
+            .map(|jump| (1.0 - damping + damping * dangling) * jump)
+            .collect::<Vec<_>>();
+        arcs.iter().for_each(|(from, to)| {
+            next[*to] += damping * ranks[*from] / out_degrees[*from] as f64;
+        });
+        next
+    })
+}
+
+/// Scores nodes by their neighbors' scores, i.e. the principal eigenvector of the adjacency
+/// matrix. Scores flow along edge directions and are normalized to unit length.
+pub fn eigenvector_centrality<G, N, E>(
+    graph: &G,
+    tolerance: f64,
+    max_iterations: usize,
+) -> IterativeScores<G>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, arcs) = indexed_arcs(graph);
+    let start = vec![1.0 / (nodes.len() as f64).sqrt(); nodes.len()];
+
+    // iterating with the adjacency matrix plus identity keeps bipartite graphs from oscillating
+    iterate(nodes, start, tolerance, max_iterations, |scores| {
+        let mut next = scores.to_vec();
+        arcs.iter()
+            .for_each(|(from, to)| next[*to] += scores[*from]);
+        let norm = next.iter().map(|score| score * score).sum::<f64>().sqrt();
+        if norm > 0.0 {
+            next.iter_mut().for_each(|score| *score /= norm);
+        }
+        next
+    })
+}
+
+/// Scores nodes by the fraction of shortest paths between other nodes which pass through them,
+/// counting edges as unit lengths. Parallel edges don't make for distinct paths. Normalization
+/// divides by the number of node pairs which don't include the node.
+pub fn betweenness_centrality<G, N, E>(graph: &G, normalized: bool) -> HashMap<NodeId<G>, f64>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, arcs) = indexed_arcs(graph);
+    let count = nodes.len();
+    let mut successors = vec![Vec::new(); count];
+    arcs.iter()
+        .filter(|(from, to)| from != to)
+        .for_each(|(from, to)| successors[*from].push(*to));
+    // parallel edges would count the paths through them multiple times
+    successors.iter_mut().for_each(|next| {
+        next.sort_unstable();
+        next.dedup();
+    });
+
+    // Brandes: count shortest paths breadth first, then accumulate dependencies backwards
+    let mut centrality = vec![0.0; count];
+    for source in 0..count {
+        let mut order = Vec::with_capacity(count);
+        let mut predecessors = vec![Vec::new(); count];
+        let mut paths = vec![0.0; count];
+        let mut distances = vec![usize::MAX; count];
+        paths[source] = 1.0;
+        distances[source] = 0;
+
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &next in &successors[node] {
+                if distances[next] == usize::MAX {
+                    distances[next] = distances[node] + 1;
+                    queue.push_back(next);
+                }
+                if distances[next] == distances[node] + 1 {
+                    paths[next] += paths[node];
+                    predecessors[next].push(node);
+                }
+            }
+        }
+
+        let mut dependencies = vec![0.0; count];
+        for &node in order.iter().rev() {
+            for &previous in &predecessors[node] {
+                dependencies[previous] +=
+                    paths[previous] / paths[node] * (1.0 + dependencies[node]);
+            }
+            if node != source {
+                centrality[node] += dependencies[node];
+            }
+        }
+    }
+
+    // undirected paths were counted once from each end
+    let mut scale = if graph.is_directed() { 1.0 } else { 0.5 };
+    if normalized && count > 2 {
+        let pairs = ((count - 1) * (count - 2)) as f64;
+        scale = if graph.is_directed() { 1.0 } else { 2.0 } * scale / pairs;
+    }
+    nodes
+        .into_iter()
+        .zip(centrality)
+        .map(|(node, score)| (node, score * scale))
+        .collect()
+}
+
+/// Scores nodes by how close they are to the nodes which can reach them, counting edges as unit
+/// lengths, so in directed graphs distances are measured along the incoming edges. The inverse
+/// average distance is scaled by the fraction of nodes which reach the node, so nodes of small
+/// components don't get inflated scores.
+pub fn closeness_centrality<G, N, E>(graph: &G) -> HashMap<NodeId<G>, f64>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, arcs) = indexed_arcs(graph);
+    let count = nodes.len();
+    let mut predecessors = vec![Vec::new(); count];
+    arcs.iter()
+        .for_each(|(from, to)| predecessors[*to].push(*from));
+
+    let scores = (0..count).map(|target| {
+        let mut distances = vec![usize::MAX; count];
+        distances[target] = 0;
+        let (mut reached, mut total) = (0usize, 0usize);
+        let mut queue = VecDeque::from([target]);
+        while let Some(node) = queue.pop_front() {
+            for &next in &predecessors[node] {
+                if distances[next] == usize::MAX {
+                    distances[next] = distances[node] + 1;
+                    reached += 1;
+                    total += distances[next];
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        if total == 0 {
+            0.0
+        } else {
+            (reached as f64 / total as f64) * (reached as f64 / (count - 1) as f64)
+        }
+    });
+    nodes.iter().copied().zip(scores).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn closeness_uses_incoming_distances() {
+        let mut graph = Graph::<u32, ()>::new();
+        let nodes = (0..6).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        for (from, to) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (0, 4)] {
+            graph.add_edge(nodes[from], nodes[to], ());
+        }
+
+        // networkx.closeness_centrality on the same graph, node 5 is isolated
+        let expected = [0.8 / 3.0, 0.8 / 3.0, 0.8 / 3.0, 0.3, 3.2 / 7.0, 0.0];
+        let scores = closeness_centrality(&graph);
+        for (node, expected) in nodes.iter().zip(expected) {
+            assert!((scores[node] - expected).abs() < 1e-12, "{node:?}");
+        }
+    }
+
+    #[test]
+    fn betweenness_ignores_parallel_edges() {
+        let mut graph = Graph::<u32, ()>::multigraph();
+        let nodes = (0..4).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        // a diamond from 0 to 3, with the path over 1 doubled
+        for (from, to) in [(0, 1), (0, 1), (0, 2), (1, 3), (2, 3)] {
+            graph.add_edge(nodes[from], nodes[to], ());
+        }
+
+        let scores = betweenness_centrality(&graph, false);
+        assert!(nodes
+            .iter()
+            .map(|node| scores[node])
+            .eq([0.0, 0.5, 0.5, 0.0]));
+        let scores = betweenness_centrality(&graph, true);
+        assert!(nodes
+            .iter()
+            .map(|node| scores[node])
+            .eq([0.0, 0.5 / 6.0, 0.5 / 6.0, 0.0]));
+    }
+}
@@ -0,0 +1,323 @@
+use std::collections::HashMap;
+
+use crate::storage::{node::NodeId, traits::GraphRead};
+
+use super::dense_indices;
+
+/// Enumerates all maximal cliques with the Bron-Kerbosch algorithm, using pivots and a degeneracy
+/// ordering of the nodes as proposed by Eppstein, Löffler and Strash. Edge directions, self loops
+/// and parallel edges are ignored. Cliques are produced lazily, so large outputs can be streamed.
+/// Isolated nodes form cliques of their own.
+pub fn maximal_cliques<G, N, E>(graph: &G) -> impl Iterator<Item = Vec<NodeId<G>>>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let (order, _) = degeneracy(&adjacency);
+    let mut ranks = vec![0; nodes.len()];
+    order
+        .iter()
+        .enumerate()
+        .for_each(|(rank, node)| ranks[*node] = rank);
+
+    // branching on the nodes in degeneracy order first keeps the candidate sets small, as every
+    // node has at most the degeneracy many neighbors later in the order
+    let mut roots = order.into_iter();
+    let mut stack = Vec::<Frame>::new();
+    std::iter::from_fn(move || loop {
+        let Some(current) = stack.last_mut() else {
+            let root = roots.next()?;
+            let (candidates, excluded): (Vec<_>, Vec<_>) = adjacency[root]
+                .iter()
+                .partition(|neighbor| ranks[**neighbor] > ranks[root]);
+            if candidates.is_empty() && excluded.is_empty() {
+                return Some(vec![nodes[root]]);
+            }
+            if !candidates.is_empty() {
+                stack.push(Frame::new(&adjacency, vec![root], candidates, excluded));
+            }
+            continue;
+        };
+        let Some(&node) = current.branches.get(current.next) else {
+            stack.pop();
+            continue;
+        };
+        current.next += 1;
+
+        let mut clique = current.clique.clone();
+        clique.push(node);
+        let within = |others: &[usize]| {
+            others
+                .iter()
+                .copied()
+                .filter(|other| adjacent(&adjacency, node, *other))
+                .collect::<Vec<_>>()
+        };
+        let (candidates, excluded) = (within(&current.candidates), within(&current.excluded));
+        current.candidates.retain(|other| *other != node);
+        current.excluded.push(node);
+
+        if !candidates.is_empty() {
+            stack.push(Frame::new(&adjacency, clique, candidates, excluded));
+        } else if excluded.is_empty() {
+            return Some(clique.into_iter().map(|node| nodes[node]).collect());
+        }
+        // otherwise an excluded node extends the clique, so it was reported already
+    })
+}
+
+// `clique` is the clique so far, `candidates` can extend it and `excluded` were tried already.
+// Only the candidates outside the neighborhood of a pivot get branched on.
+struct Frame {
+    clique: Vec<usize>,
+    candidates: Vec<usize>,
+    excluded: Vec<usize>,
+    branches: Vec<usize>,
+    next: usize,
+}
+
+impl Frame {
+    fn new(
+        adjacency: &[Vec<usize>],
+        clique: Vec<usize>,
+        candidates: Vec<usize>,
+        excluded: Vec<usize>,
+    ) -> Self {
+        // the pivot with the most candidate neighbors leaves the fewest branches
+        let pivot = candidates
+            .iter()
+            .chain(&excluded)
+            .max_by_key(|pivot| {
+                candidates
+                    .iter()
+                    .filter(|node| adjacent(adjacency, **pivot, **node))
+                    .count()
+            })
+            .copied();
+        let branches = candidates
+            .iter()
+            .copied()
+            .filter(|node| pivot.is_none_or(|pivot| !adjacent(adjacency, pivot, *node)))
+            .collect();
+        Self {
+            clique,
+            candidates,
+            excluded,
+            branches,
+            next: 0,
+        }
+    }
+}
+
+#[inline]
+fn adjacent(adjacency: &[Vec<usize>], a: usize, b: usize) -> bool {
+    adjacency[a].binary_search(&b).is_ok()
+}
+
+/// Computes the core number of every node, i.e. the largest `k` so that the node belongs to a
+/// subgraph where all nodes have at least `k` neighbors. Edge directions, self loops and parallel
+/// edges are ignored.
+pub fn core_numbers<G, N, E>(graph: &G) -> HashMap<NodeId<G>, usize>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let (_, cores) = degeneracy(&adjacency);
+    nodes.into_iter().zip(cores).collect()
+}
+
+// Batagelj-Zaversnik: keeps the nodes sorted by their remaining degree with bucket bounds, and
+// peels off the node with the lowest degree while moving its neighbors one bucket down. Returns
+// the peeling order and the core numbers.
+fn degeneracy(adjacency: &[Vec<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let mut degrees = adjacency.iter().map(Vec::len).collect::<Vec<_>>();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+    let mut starts = vec![0; max_degree + 1];
+    degrees.iter().for_each(|degree| starts[*degree] += 1);
+    let mut start = 0;
+    for bucket in starts.iter_mut() {
+        let size = *bucket;
+        *bucket = start;
+        start += size;
+    }
+    let mut positions = vec![0; adjacency.len()];
+    let mut order = vec![0; adjacency.len()];
+    for (node, degree) in degrees.iter().enumerate() {
+        positions[node] = starts[*degree];
+        order[positions[node]] = node;
+        starts[*degree] += 1;
+    }
+    for degree in (1..=max_degree).rev() {
+        starts[degree] = starts[degree - 1];
+    }
+    starts[0] = 0;
+
+    for index in 0..order.len() {
+        let node = order[index];
+        for &neighbor in &adjacency[node] {
+            if degrees[neighbor] > degrees[node] {
+                // swap the neighbor to the front of its bucket, then shrink the bucket past it
+                let degree = degrees[neighbor];
+                let front = order[starts[degree]];
+                if front != neighbor {
+                    order.swap(positions[neighbor], starts[degree]);
+                    positions.swap(neighbor, front);
+                }
+                starts[degree] += 1;
+                degrees[neighbor] -= 1;
+            }
+        }
+    }
+    (order, degrees)
+}
+
+/// Nodes of the `k`-core, the largest subgraph in which every node has at least `k` neighbors,
+/// in graph order
+pub fn k_core<G, N, E>(graph: &G, k: usize) -> Vec<NodeId<G>>
+where
+    G: GraphRead<N, E>,
+{
+    let cores = core_numbers(graph);
+    graph.nodes().filter(|node| cores[node] >= k).collect()
+}
+
+/// Counts the triangles every node is part of, ignoring edge directions, self loops and parallel
+/// edges
+pub fn triangles<G, N, E>(graph: &G) -> HashMap<NodeId<G>, usize>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, adjacency) = simple_adjacency(graph);
+    let mut counts = vec![0; nodes.len()];
+    // every triangle is found once from its lowest node through its middle one
+    for (first, neighbors) in adjacency.iter().enumerate() {
+        for &second in neighbors.iter().filter(|second| **second > first) {
+            for &third in adjacency[second].iter().filter(|third| **third > second) {
+                if neighbors.binary_search(&third).is_ok() {
+                    counts[first] += 1;
+                    counts[second] += 1;
+                    counts[third] += 1;
+                }
+            }
+        }
+    }
+    nodes.into_iter().zip(counts).collect()
+}
+
+/// Counts the triangles of the graph, ignoring edge directions, self loops and parallel edges
+pub fn triangle_count<G, N, E>(graph: &G) -> usize
+where
+    G: GraphRead<N, E>,
+{
+    triangles(graph).values().sum::<usize>() / 3
+}
+
+// sorted distinct neighbors by dense index, without self loops
+fn simple_adjacency<G, N, E>(graph: &G) -> (Vec<NodeId<G>>, Vec<Vec<usize>>)
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, indices) = dense_indices(graph);
+    let adjacency = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| {
+            let mut neighbors = graph
+                .neighbor_nodes(*node)
+                .map(|neighbor| indices[&neighbor])
+                .filter(|neighbor| *neighbor != index)
+                .collect::<Vec<_>>();
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            neighbors
+        })
+        .collect();
+    (nodes, adjacency)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn cliques() {
+        // a complete graph on 0 to 3, a triangle 3, 4, 5 with a tail to 6, and 7 on its own
+        let mut graph = Graph::<usize, (), Undirected>::multigraph().with_self_loops(true);
+        let nodes = (0..8).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        for (from, to) in [
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (5, 6),
+            // neither counts as a neighbor
+            (1, 0),
+            (6, 6),
+        ] {
+            graph.add_edge(nodes[from], nodes[to], ()).unwrap();
+        }
+
+        let mut cliques = maximal_cliques(&graph)
+            .map(|clique| {
+                let mut clique = clique
+                    .into_iter()
+                    .map(|node| graph[node])
+                    .collect::<Vec<_>>();
+                clique.sort_unstable();
+                clique
+            })
+            .collect::<Vec<_>>();
+        cliques.sort_unstable();
+        assert_eq!(
+            cliques,
+            [vec![0, 1, 2, 3], vec![3, 4, 5], vec![5, 6], vec![7]]
+        );
+    }
+
+    #[test]
+    fn directed_cliques() {
+        let mut graph = Graph::<usize, ()>::new();
+        let nodes = (0..3).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        for (from, to) in [(0, 1), (1, 2), (2, 0)] {
+            graph.add_edge(nodes[from], nodes[to], ()).unwrap();
+        }
+        let cliques = maximal_cliques(&graph).collect::<Vec<_>>();
+        assert_eq!(cliques.len(), 1);
+        assert_eq!(
+            cliques[0].iter().copied().collect::<HashSet<_>>(),
+            HashSet::from_iter(nodes)
+        );
+    }
+
+    #[test]
+    fn cores_and_triangles() {
+        // a complete graph on 0 to 3 with a tail to 4, and 5 on its own
+        let mut graph = Graph::<usize, (), Undirected>::new();
+        let nodes = (0..6).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        for (from, to) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3), (3, 4)] {
+            graph.add_edge(nodes[from], nodes[to], ()).unwrap();
+        }
+
+        let cores = core_numbers(&graph);
+        assert!(nodes.iter().map(|node| cores[node]).eq([3, 3, 3, 3, 1, 0]));
+        assert_eq!(k_core(&graph, 3), nodes[..4]);
+        assert_eq!(k_core(&graph, 1), nodes[..5]);
+        assert_eq!(k_core(&graph, 0), nodes);
+        assert_eq!(k_core(&graph, 4), []);
+
+        let triangles = triangles(&graph);
+        assert!(nodes
+            .iter()
+            .map(|node| triangles[node])
+            .eq([3, 3, 3, 3, 0, 0]));
+        assert_eq!(triangle_count(&graph), 4);
+    }
+}
@@ -0,0 +1,430 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::storage::{edge::EdgeId, node::NodeId, traits::GraphRead};
+
+use super::dense_indices;
+
+// stop merging levels once they improve the modularity by less than this
+const LOUVAIN_THRESHOLD: f64 = 1e-7;
+
+/// Community of every node together with the modularity of the partition
+pub struct Communities<G> {
+    labels: HashMap<NodeId<G>, usize>,
+    count: usize,
+    modularity: f64,
+}
+
+impl<G> Communities<G> {
+    #[inline]
+    pub fn community(&self, node: NodeId<G>) -> Option<usize> {
+        self.labels.get(&node).copied()
+    }
+
+    /// Community labels, counting up from zero in the order the communities first appear in the
+    /// graph
+    #[inline]
+    pub fn communities(&self) -> &HashMap<NodeId<G>, usize> {
+        &self.labels
+    }
+
+    #[inline]
+    pub fn into_communities(self) -> HashMap<NodeId<G>, usize> {
+        self.labels
+    }
+
+    /// Number of communities
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    #[inline]
+    pub fn modularity(&self) -> f64 {
+        self.modularity
+    }
+}
+
+impl<G> Clone for Communities<G> {
+    fn clone(&self) -> Self {
+        Self {
+            labels: self.labels.clone(),
+            count: self.count,
+            modularity: self.modularity,
+        }
+    }
+}
+
+impl<G> Debug for Communities<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Communities")
+            .field("labels", &self.labels)
+            .field("count", &self.count)
+            .field("modularity", &self.modularity)
+            .finish()
+    }
+}
+
+/// Measures how much more weight lies within the communities than expected if the edges were
+/// rewired at random while keeping the weighted degrees, ignoring edge directions. Nodes without a
+/// community count as communities of their own.
+pub fn modularity<G, N, E>(
+    graph: &G,
+    communities: &HashMap<NodeId<G>, usize>,
+    weight: impl FnMut(EdgeId<G>, &E) -> f64,
+) -> f64
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, network) = Network::new(graph, weight);
+    let mut count = 0;
+    let mut fresh = || {
+        count += 1;
+        count - 1
+    };
+    let mut dense = HashMap::new();
+    let labels = nodes
+        .iter()
+        .map(|node| match communities.get(node) {
+            Some(label) => *dense.entry(*label).or_insert_with(&mut fresh),
+            None => fresh(),
+        })
+        .collect::<Vec<_>>();
+    network.modularity(&labels)
+}
+
+/// Finds communities with the Louvain method, which moves single nodes between communities as
+/// long as that increases the modularity and then repeats on the graph of the communities. Edge
+/// directions are ignored and weights should be positive. The seed fixes the order the nodes are
+/// visited in, so equal seeds give equal results.
+pub fn louvain<G, N, E>(
+    graph: &G,
+    weight: impl FnMut(EdgeId<G>, &E) -> f64,
+    seed: u64,
+) -> Communities<G>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, network) = Network::new(graph, weight);
+    let mut random = Random(seed);
+    let mut labels = (0..nodes.len()).collect::<Vec<_>>();
+    if network.total <= 0.0 {
+        return communities(nodes, &network, labels);
+    }
+
+    let mut level = network.clone();
+    let mut modularity = level.modularity(&labels);
+    loop {
+        let (communities, count) = level.move_nodes(&mut random);
+        // every node stayed on its own
+        if count == level.len() {
+            break;
+        }
+        labels
+            .iter_mut()
+            .for_each(|label| *label = communities[*label]);
+        let next = level.modularity(&communities);
+        level = level.aggregate(&communities, count);
+        if next - modularity < LOUVAIN_THRESHOLD {
+            break;
+        }
+        modularity = next;
+    }
+
+    communities(nodes, &network, labels)
+}
+
+/// Finds communities with asynchronous label propagation: nodes repeatedly adopt the label with
+/// the most weight among their neighbors until no node can, breaking ties at random. Edge
+/// directions are ignored and weights should be positive. The seed fixes the visiting order and
+/// the tie breaks, so equal seeds give equal results.
+pub fn label_propagation<G, N, E>(
+    graph: &G,
+    weight: impl FnMut(EdgeId<G>, &E) -> f64,
+    seed: u64,
+) -> Communities<G>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, network) = Network::new(graph, weight);
+    let mut random = Random(seed);
+    let mut labels = (0..nodes.len()).collect::<Vec<_>>();
+    let mut order = labels.clone();
+    let mut weights = Sums::new(nodes.len());
+    let mut best = Vec::new();
+
+    // keeping a label among the best ones makes every change strictly increase the weight within
+    // labels, so this terminates
+    let mut changed = true;
+    while changed {
+        changed = false;
+        random.shuffle(&mut order);
+        for &node in &order {
+            for &(neighbor, weight) in &network.neighbors[node] {
+                weights.add(labels[neighbor], weight);
+            }
+            let max = weights
+                .indices()
+                .iter()
+                .map(|label| weights.get(*label))
+                .reduce(f64::max);
+            if let Some(max) = max {
+                let labels_with_max = weights.indices().iter().copied();
+                best.extend(labels_with_max.filter(|label| weights.get(*label) == max));
+                if !best.contains(&labels[node]) {
+                    labels[node] = best[random.below(best.len())];
+                    changed = true;
+                }
+            }
+            weights.clear();
+            best.clear();
+        }
+    }
+
+    communities(nodes, &network, labels)
+}
+
+fn communities<G>(nodes: Vec<NodeId<G>>, network: &Network, labels: Vec<usize>) -> Communities<G> {
+    let (labels, count) = relabel(&labels);
+    Communities {
+        modularity: network.modularity(&labels),
+        labels: nodes.into_iter().zip(labels).collect(),
+        count,
+    }
+}
+
+// renumbers labels from zero in the order they first appear
+fn relabel(labels: &[usize]) -> (Vec<usize>, usize) {
+    let mut dense = HashMap::new();
+    let labels = labels
+        .iter()
+        .map(|label| {
+            let next = dense.len();
+            *dense.entry(*label).or_insert(next)
+        })
+        .collect();
+    (labels, dense.len())
+}
+
+// undirected weighted graph over dense indices. Every edge shows up in the neighbors of both ends,
+// self loops are kept apart.
+#[derive(Clone)]
+struct Network {
+    neighbors: Vec<Vec<(usize, f64)>>,
+    loops: Vec<f64>,
+    degrees: Vec<f64>,
+    total: f64,
+}
+
+impl Network {
+    fn new<G, N, E>(
+        graph: &G,
+        mut weight: impl FnMut(EdgeId<G>, &E) -> f64,
+    ) -> (Vec<NodeId<G>>, Self)
+    where
+        G: GraphRead<N, E>,
+    {
+        let (nodes, indices) = dense_indices(graph);
+        let mut network = Self {
+            neighbors: vec![Vec::new(); nodes.len()],
+            loops: vec![0.0; nodes.len()],
+            degrees: vec![0.0; nodes.len()],
+            total: 0.0,
+        };
+        for edge in graph.edges() {
+            let (Some(ends), Some(value)) = (graph.edge_ends(edge), graph.edge(edge)) else {
+                continue;
+            };
+            let (from, to) = (indices[&ends.from], indices[&ends.to]);
+            let weight = weight(edge, value);
+            if from == to {
+                network.loops[from] += weight;
+            } else {
+                network.neighbors[from].push((to, weight));
+                network.neighbors[to].push((from, weight));
+            }
+            network.degrees[from] += weight;
+            network.degrees[to] += weight;
+            network.total += weight;
+        }
+        (nodes, network)
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    fn modularity(&self, labels: &[usize]) -> f64 {
+        if self.total <= 0.0 {
+            return 0.0;
+        }
+        let count = labels.iter().max().map_or(0, |max| max + 1);
+        let mut internal = vec![0.0; count];
+        let mut degrees = vec![0.0; count];
+        for (node, neighbors) in self.neighbors.iter().enumerate() {
+            let label = labels[node];
+            // internal edges are seen from both ends
+            internal[label] += self.loops[node]
+                + neighbors
+                    .iter()
+                    .filter(|(neighbor, _)| labels[*neighbor] == label)
+                    .map(|(_, weight)| weight / 2.0)
+                    .sum::<f64>();
+            degrees[label] += self.degrees[node];
+        }
+        internal
+            .iter()
+            .zip(degrees)
+            .map(|(internal, degree)| internal / self.total - (degree / (2.0 * self.total)).powi(2))
+            .sum()
+    }
+
+    // moves nodes into the neighboring community with the best modularity gain until none moves,
+    // returning the dense communities and their count
+    fn move_nodes(&self, random: &mut Random) -> (Vec<usize>, usize) {
+        let mut communities = (0..self.len()).collect::<Vec<_>>();
+        let mut totals = self.degrees.clone();
+        let mut order = communities.clone();
+        random.shuffle(&mut order);
+        let mut weights = Sums::new(self.len());
+
+        let mut moved = true;
+        while moved {
+            moved = false;
+            for &node in &order {
+                let current = communities[node];
+                let degree = self.degrees[node];
+                for &(neighbor, weight) in &self.neighbors[node] {
+                    weights.add(communities[neighbor], weight);
+                }
+
+                // gain of joining a community, up to a factor and the cost of leaving the old one
+                totals[current] -= degree;
+                let gain = |community: usize| {
+                    weights.get(community) - totals[community] * degree / (2.0 * self.total)
+                };
+                let mut best = (current, gain(current));
+                for &community in weights.indices() {
+                    let gain = gain(community);
+                    if gain > best.1 {
+                        best = (community, gain);
+                    }
+                }
+                totals[best.0] += degree;
+                if best.0 != current {
+                    communities[node] = best.0;
+                    moved = true;
+                }
+                weights.clear();
+            }
+        }
+        relabel(&communities)
+    }
+
+    // merges every community into a single node which keeps the internal weight as a self loop
+    fn aggregate(&self, communities: &[usize], count: usize) -> Self {
+        let mut members = vec![Vec::new(); count];
+        communities
+            .iter()
+            .enumerate()
+            .for_each(|(node, community)| members[*community].push(node));
+
+        let mut network = Self {
+            neighbors: vec![Vec::new(); count],
+            loops: vec![0.0; count],
+            degrees: vec![0.0; count],
+            total: self.total,
+        };
+        let mut weights = Sums::new(count);
+        for (community, members) in members.iter().enumerate() {
+            for &node in members {
+                network.loops[community] += self.loops[node];
+                network.degrees[community] += self.degrees[node];
+                for &(neighbor, weight) in &self.neighbors[node] {
+                    let other = communities[neighbor];
+                    if other == community {
+                        network.loops[community] += weight / 2.0;
+                    } else {
+                        weights.add(other, weight);
+                    }
+                }
+            }
+            network.neighbors[community] = weights
+                .indices()
+                .iter()
+                .map(|other| (*other, weights.get(*other)))
+                .collect();
+            weights.clear();
+        }
+        network
+    }
+}
+
+// sums weights per index and remembers the order the indices first showed up in, so iterating
+// them doesn't depend on hashing
+struct Sums {
+    sums: Vec<f64>,
+    present: Vec<bool>,
+    indices: Vec<usize>,
+}
+
+impl Sums {
+    fn new(len: usize) -> Self {
+        Self {
+            sums: vec![0.0; len],
+            present: vec![false; len],
+            indices: Vec::new(),
+        }
+    }
+
+    #[inline]
+    fn add(&mut self, index: usize, weight: f64) {
+        if !self.present[index] {
+            self.present[index] = true;
+            self.indices.push(index);
+        }
+        self.sums[index] += weight;
+    }
+
+    #[inline]
+    fn get(&self, index: usize) -> f64 {
+        self.sums[index]
+    }
+
+    #[inline]
+    fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    fn clear(&mut self) {
+        for index in self.indices.drain(..) {
+            self.sums[index] = 0.0;
+            self.present[index] = false;
+        }
+    }
+}
+
+// splitmix64, good enough to shuffle reproducibly without pulling in a dependency
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    #[inline]
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.below(index + 1));
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+use crate::storage::{edge::EdgeId, node::NodeId, traits::GraphRead};
+
+use super::opposite;
+
+/// Disjoint sets of nodes which are merged as edges get added to a graph
+///
+/// Nodes join the structure on their first use. Removing nodes or edges isn't supported since sets
+/// can't be split again, so rebuild it with [`UnionFind::from_graph`] in that case.
+pub struct UnionFind<G> {
+    indices: HashMap<NodeId<G>, usize>,
+    nodes: Vec<NodeId<G>>,
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    set_count: usize,
+}
+
+impl<G> UnionFind<G> {
+    pub fn new() -> Self {
+        Self {
+            indices: HashMap::new(),
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            ranks: Vec::new(),
+            set_count: 0,
+        }
+    }
+
+    /// Puts every node of the graph into the set of the nodes it is connected to, ignoring the
+    /// direction of edges
+    pub fn from_graph<N, E>(graph: &G) -> Self
+    where
+        G: GraphRead<N, E>,
+    {
+        let mut union_find = Self::new();
+        graph.nodes().for_each(|node| {
+            union_find.insert(node);
+        });
+        graph
+            .edges()
+            .filter_map(|edge| graph.edge_ends(edge))
+            .for_each(|ends| {
+                union_find.union(ends.from, ends.to);
+            });
+        union_find
+    }
+
+    /// Adds the node as a set of its own, returns whether it wasn't known before
+    pub fn insert(&mut self, node: NodeId<G>) -> bool {
+        if self.indices.contains_key(&node) {
+            return false;
+        }
+        self.index(node);
+        true
+    }
+
+    /// Representative of the set which contains the node
+    pub fn find(&mut self, node: NodeId<G>) -> NodeId<G> {
+        let index = self.index(node);
+        let root = self.root(index);
+        self.nodes[root]
+    }
+
+    /// Merges the sets of both nodes, returns whether they were disjoint before
+    pub fn union(&mut self, a: NodeId<G>, b: NodeId<G>) -> bool {
+        let (a, b) = (self.index(a), self.index(b));
+        let (a, b) = (self.root(a), self.root(b));
+        if a == b {
+            return false;
+        }
+
+        let (child, parent) = if self.ranks[a] < self.ranks[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.parents[child] = parent;
+        if self.ranks[child] == self.ranks[parent] {
+            self.ranks[parent] += 1;
+        }
+        self.set_count -= 1;
+        true
+    }
+
+    #[inline]
+    pub fn connected(&mut self, a: NodeId<G>, b: NodeId<G>) -> bool {
+        a == b || self.find(a) == self.find(b)
+    }
+
+    /// Number of nodes which are part of the structure
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Number of disjoint sets
+    #[inline]
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    fn index(&mut self, node: NodeId<G>) -> usize {
+        *self.indices.entry(node).or_insert_with(|| {
+            self.nodes.push(node);
+            self.parents.push(self.parents.len());
+            self.ranks.push(0);
+            self.set_count += 1;
+            self.nodes.len() - 1
+        })
+    }
+
+    // path halving keeps the trees flat without recursion
+    fn root(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+}
+
+impl<G> Default for UnionFind<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<G> Clone for UnionFind<G> {
+    fn clone(&self) -> Self {
+        Self {
+            indices: self.indices.clone(),
+            nodes: self.nodes.clone(),
+            parents: self.parents.clone(),
+            ranks: self.ranks.clone(),
+            set_count: self.set_count,
+        }
+    }
+}
+
+impl<G> Debug for UnionFind<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnionFind")
+            .field("nodes", &self.nodes)
+            .field("parents", &self.parents)
+            .field("set_count", &self.set_count)
+            .finish()
+    }
+}
+
+/// Labels every node with the connected component it belongs to, ignoring the direction of edges.
+/// Labels count up from zero in the order the components first appear in the graph.
+pub fn connected_components<G, N, E>(graph: &G) -> HashMap<NodeId<G>, usize>
+where
+    G: GraphRead<N, E>,
+{
+    let mut union_find = UnionFind::from_graph(graph);
+    let mut labels = HashMap::new();
+    graph
+        .nodes()
+        .map(|node| {
+            let root = union_find.find(node);
+            let label = labels.len();
+            (node, *labels.entry(root).or_insert(label))
+        })
+        .collect()
+}
+
+/// Nodes whose removal disconnects their connected component, ignoring the direction of edges
+pub fn articulation_points<G, N, E>(graph: &G) -> HashSet<NodeId<G>>
+where
+    G: GraphRead<N, E>,
+{
+    biconnectivity(graph).articulation_points
+}
+
+/// Edges whose removal disconnects their connected component, ignoring the direction of edges.
+/// Parallel edges are never bridges.
+pub fn bridges<G, N, E>(graph: &G) -> HashSet<EdgeId<G>>
+where
+    G: GraphRead<N, E>,
+{
+    biconnectivity(graph).bridges
+}
+
+/// Splits the edges into maximal sets which stay connected after removing any single node,
+/// ignoring the direction of edges. Articulation points are shared by the components they
+/// separate, bridges form components of their own and self loops belong to none.
+pub fn biconnected_components<G, N, E>(graph: &G) -> Vec<HashSet<EdgeId<G>>>
+where
+    G: GraphRead<N, E>,
+{
+    biconnectivity(graph).components
+}
+
+struct Biconnectivity<G> {
+    articulation_points: HashSet<NodeId<G>>,
+    bridges: HashSet<EdgeId<G>>,
+    components: Vec<HashSet<EdgeId<G>>>,
+}
+
+// Hopcroft-Tarjan: a depth first search where `low` is the earliest discovered node reachable
+// through the subtree of a node and one back edge
+fn biconnectivity<G, N, E>(graph: &G) -> Biconnectivity<G>
+where
+    G: GraphRead<N, E>,
+{
+    let mut result = Biconnectivity {
+        articulation_points: HashSet::new(),
+        bridges: HashSet::new(),
+        components: Vec::new(),
+    };
+    let mut discovered = HashMap::with_capacity(graph.node_count());
+    let mut low = HashMap::with_capacity(graph.node_count());
+    let mut edges = Vec::new();
+    let neighbors = |node: NodeId<G>| {
+        graph
+            .neighbor_edges(node)
+            .filter_map(|edge| Some((edge, opposite(graph, edge, node)?)))
+            .filter(|(_, neighbor)| *neighbor != node)
+            .collect::<Vec<_>>()
+    };
+
+    for root in graph.nodes() {
+        if discovered.contains_key(&root) {
+            continue;
+        }
+        discovered.insert(root, discovered.len());
+        low.insert(root, discovered[&root]);
+        let mut root_children = 0;
+        let mut stack = vec![(root, None, neighbors(root), 0)];
+
+        while let Some((node, parent_edge, adjacent, next)) = stack.last_mut() {
+            let node = *node;
+            if let Some(&(edge, neighbor)) = adjacent.get(*next) {
+                *next += 1;
+                // parallel edges to the parent are back edges, only the tree edge itself isn't
+                if Some(edge) == *parent_edge {
+                    continue;
+                }
+                match discovered.get(&neighbor).copied() {
+                    None => {
+                        let index = discovered.len();
+                        discovered.insert(neighbor, index);
+                        low.insert(neighbor, index);
+                        edges.push(edge);
+                        if node == root {
+                            root_children += 1;
+                        }
+                        stack.push((neighbor, Some(edge), neighbors(neighbor), 0));
+                    }
+                    // back edges show up again from their other end, which was discovered later
+                    Some(index) if index < discovered[&node] => {
+                        edges.push(edge);
+                        low.insert(node, low[&node].min(index));
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            let parent_edge = *parent_edge;
+            stack.pop();
+            let (Some(edge), Some((parent, ..))) = (parent_edge, stack.last()) else {
+                continue;
+            };
+            let parent = *parent;
+            low.insert(parent, low[&parent].min(low[&node]));
+            if low[&node] > discovered[&parent] {
+                result.bridges.insert(edge);
+            }
+            // nothing below the node reaches above the parent, so the parent separates them
+            if low[&node] >= discovered[&parent] {
+                if parent != root {
+                    result.articulation_points.insert(parent);
+                }
+                let start = edges
+                    .iter()
+                    .rposition(|other| *other == edge)
+                    .expect("tree edges stay on the stack until their subtree is done");
+                result.components.push(edges.drain(start..).collect());
+            }
+        }
+
+        if root_children > 1 {
+            result.articulation_points.insert(root);
+        }
+    }
+
+    result
+}
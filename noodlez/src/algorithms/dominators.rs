@@ -0,0 +1,302 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+use crate::storage::{node::NodeId, traits::GraphRead};
+
+use super::positions;
+
+/// Dominator tree of all nodes which are reachable from a root node
+///
+/// A node dominates another one if every path from the root to the other node passes through it.
+/// For post dominators the paths lead from the other node to the root instead.
+pub struct Dominators<G> {
+    root: NodeId<G>,
+    immediate: HashMap<NodeId<G>, NodeId<G>>,
+    children: HashMap<NodeId<G>, Vec<NodeId<G>>>,
+    frontiers: HashMap<NodeId<G>, Vec<NodeId<G>>>,
+}
+
+impl<G> Dominators<G> {
+    #[inline]
+    pub fn root(&self) -> NodeId<G> {
+        self.root
+    }
+
+    /// Closest strict dominator of the node. The root and unreachable nodes have none.
+    #[inline]
+    pub fn immediate_dominator(&self, node: NodeId<G>) -> Option<NodeId<G>> {
+        self.immediate.get(&node).copied()
+    }
+
+    #[inline]
+    pub fn is_reachable(&self, node: NodeId<G>) -> bool {
+        node == self.root || self.immediate.contains_key(&node)
+    }
+
+    /// All dominators of a reachable node, starting with the node itself and ending with the root
+    pub fn dominators(&self, node: NodeId<G>) -> Option<impl Iterator<Item = NodeId<G>> + '_> {
+        self.is_reachable(node)
+            .then(|| std::iter::successors(Some(node), |node| self.immediate_dominator(*node)))
+    }
+
+    /// Whether every path to `b` passes through `a`, which holds for `a == b` as well
+    pub fn dominates(&self, a: NodeId<G>, b: NodeId<G>) -> bool {
+        self.dominators(b)
+            .is_some_and(|mut dominators| dominators.any(|dominator| dominator == a))
+    }
+
+    /// Nodes whose immediate dominator is the given node
+    #[inline]
+    pub fn children(&self, node: NodeId<G>) -> &[NodeId<G>] {
+        self.children.get(&node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Walks the dominator tree from the root, visiting every node before its children
+    pub fn preorder(&self) -> impl Iterator<Item = NodeId<G>> + '_ {
+        let mut stack = Vec::from([self.root]);
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(self.children(node).iter().rev());
+            Some(node)
+        })
+        .filter(|node| self.is_reachable(*node))
+    }
+
+    /// Nodes where the dominance of the given node ends, i.e. which it doesn't strictly dominate
+    /// although it dominates one of their predecessors
+    #[inline]
+    pub fn frontier(&self, node: NodeId<G>) -> &[NodeId<G>] {
+        self.frontiers.get(&node).map_or(&[], Vec::as_slice)
+    }
+}
+
+impl<G> Clone for Dominators<G> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            immediate: self.immediate.clone(),
+            children: self.children.clone(),
+            frontiers: self.frontiers.clone(),
+        }
+    }
+}
+
+impl<G> Debug for Dominators<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dominators")
+            .field("root", &self.root)
+            .field("immediate", &self.immediate)
+            .field("frontiers", &self.frontiers)
+            .finish()
+    }
+}
+
+/// Computes the dominators of all nodes reachable from the root with the algorithm of Cooper,
+/// Harvey and Kennedy
+pub fn dominators<G, N, E>(graph: &G, root: NodeId<G>) -> Dominators<G>
+where
+    G: GraphRead<N, E>,
+{
+    compute(graph, root, false)
+}
+
+/// Computes the post dominators of all nodes which reach the exit, i.e. the dominators on the
+/// reversed graph
+pub fn post_dominators<G, N, E>(graph: &G, exit: NodeId<G>) -> Dominators<G>
+where
+    G: GraphRead<N, E>,
+{
+    compute(graph, exit, true)
+}
+
+fn compute<G, N, E>(graph: &G, root: NodeId<G>, reversed: bool) -> Dominators<G>
+where
+    G: GraphRead<N, E>,
+{
+    let mut dominators = Dominators {
+        root,
+        immediate: HashMap::new(),
+        children: HashMap::new(),
+        frontiers: HashMap::new(),
+    };
+    if !graph.contains_node(root) {
+        return dominators;
+    }
+    let adjacent = |node: NodeId<G>, forward: bool| -> Vec<NodeId<G>> {
+        if forward != reversed {
+            graph.outcoming_nodes(node).collect()
+        } else {
+            graph.incoming_nodes(node).collect()
+        }
+    };
+
+    // number the reachable nodes in postorder, so the root gets the highest number
+    let mut postorder = Vec::new();
+    let mut visited = HashSet::from([root]);
+    let mut stack = vec![(root, adjacent(root, true), 0)];
+    while let Some((node, successors, next)) = stack.last_mut() {
+        match successors.get(*next).copied() {
+            Some(successor) => {
+                *next += 1;
+                if visited.insert(successor) {
+                    stack.push((successor, adjacent(successor, true), 0));
+                }
+            }
+            None => {
+                postorder.push(*node);
+                stack.pop();
+            }
+        }
+    }
+    let indices = positions(&postorder);
+    let predecessors = postorder
+        .iter()
+        .map(|node| {
+            adjacent(*node, false)
+                .into_iter()
+                .filter_map(|predecessor| indices.get(&predecessor).copied())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let root_index = postorder.len() - 1;
+    let mut immediate = vec![usize::MAX; postorder.len()];
+    immediate[root_index] = root_index;
+    // walks up from both nodes until the paths meet, nodes higher up have higher numbers
+    let intersect = |immediate: &[usize], mut a: usize, mut b: usize| {
+        while a != b {
+            while a < b {
+                a = immediate[a];
+            }
+            while b < a {
+                b = immediate[b];
+            }
+        }
+        a
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for node in (0..root_index).rev() {
+            let new = predecessors[node]
+                .iter()
+                .copied()
+                .filter(|predecessor| immediate[*predecessor] != usize::MAX)
+                .reduce(|a, b| intersect(&immediate, a, b))
+                .expect("reachable nodes have a processed predecessor");
+            if immediate[node] != new {
+                immediate[node] = new;
+                changed = true;
+            }
+        }
+    }
+
+    // every predecessor up to the immediate dominator has the node in its frontier
+    let mut frontiers = vec![Vec::new(); postorder.len()];
+    for (node, predecessors) in predecessors.iter().enumerate() {
+        for &predecessor in predecessors {
+            let mut runner = predecessor;
+            while node == root_index || runner != immediate[node] {
+                if !frontiers[runner].contains(&node) {
+                    frontiers[runner].push(node);
+                }
+                if runner == root_index {
+                    break;
+                }
+                runner = immediate[runner];
+            }
+        }
+    }
+
+    for (index, node) in postorder.iter().enumerate().rev() {
+        if index != root_index {
+            let dominator = postorder[immediate[index]];
+            dominators.immediate.insert(*node, dominator);
+            dominators
+                .children
+                .entry(dominator)
+                .or_default()
+                .push(*node);
+        }
+        if !frontiers[index].is_empty() {
+            let frontier = frontiers[index].iter().map(|index| postorder[*index]);
+            dominators.frontiers.insert(*node, frontier.collect());
+        }
+    }
+    dominators
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn dominator_tree() {
+        // 0 branches to 1 and 2 which join at 3, 4 loops back to 1 and 5 jumps in from outside
+        let mut graph = Graph::<usize, ()>::new();
+        let nodes = (0..7).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        for (from, to) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 1), (5, 3)] {
+            graph.add_edge(nodes[from], nodes[to], ()).unwrap();
+        }
+
+        let dominators = dominators(&graph, nodes[0]);
+        let idoms = nodes
+            .iter()
+            .map(|node| dominators.immediate_dominator(*node));
+        assert!(
+            idoms.eq([None, Some(0), Some(0), Some(0), Some(3), None, None]
+                .map(|idom| idom.map(|idom| nodes[idom])))
+        );
+        assert_eq!(
+            dominators
+                .children(nodes[0])
+                .iter()
+                .copied()
+                .collect::<HashSet<_>>(),
+            HashSet::from([nodes[1], nodes[2], nodes[3]])
+        );
+        assert!(dominators
+            .dominators(nodes[4])
+            .unwrap()
+            .eq([nodes[4], nodes[3], nodes[0]]));
+        assert!(dominators.dominates(nodes[3], nodes[4]));
+        assert!(!dominators.dominates(nodes[1], nodes[3]));
+        assert_eq!(dominators.preorder().count(), 5);
+
+        assert_eq!(dominators.frontier(nodes[0]), []);
+        assert_eq!(dominators.frontier(nodes[1]), [nodes[3]]);
+        assert_eq!(dominators.frontier(nodes[2]), [nodes[3]]);
+        assert_eq!(dominators.frontier(nodes[3]), [nodes[1]]);
+        assert_eq!(dominators.frontier(nodes[4]), [nodes[1]]);
+
+        // neither the node jumping in nor the isolated one is reachable from the root
+        for node in [nodes[5], nodes[6]] {
+            assert!(!dominators.is_reachable(node));
+            assert!(dominators.dominators(node).is_none());
+            assert!(!dominators.dominates(nodes[0], node));
+            assert!(!dominators.dominates(node, node));
+            assert_eq!(dominators.frontier(node), []);
+            assert_eq!(dominators.children(node), []);
+        }
+    }
+
+    #[test]
+    fn post_dominator_tree() {
+        // a diamond from 0 to 3 with a dead end 4 behind the exit
+        let mut graph = Graph::<usize, ()>::new();
+        let nodes = (0..5).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        for (from, to) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)] {
+            graph.add_edge(nodes[from], nodes[to], ()).unwrap();
+        }
+
+        let post_dominators = post_dominators(&graph, nodes[3]);
+        let ipdoms = nodes
+            .iter()
+            .map(|node| post_dominators.immediate_dominator(*node));
+        assert!(ipdoms.eq([Some(nodes[3]), Some(nodes[3]), Some(nodes[3]), None, None]));
+        assert!(!post_dominators.is_reachable(nodes[4]));
+        assert_eq!(post_dominators.frontier(nodes[1]), [nodes[0]]);
+    }
+}
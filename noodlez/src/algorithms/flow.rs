@@ -0,0 +1,461 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+
+use crate::storage::{edge::EdgeId, node::NodeId, traits::GraphRead};
+
+use super::{
+    dense_indices,
+    measure::{FlowMeasure, Measure},
+};
+
+/// Maximum flow between two nodes together with a minimum cut separating them
+pub struct MaxFlow<G, C> {
+    value: C,
+    flows: HashMap<EdgeId<G>, (NodeId<G>, C)>,
+    source_side: HashSet<NodeId<G>>,
+}
+
+impl<G, C: Copy + Default> MaxFlow<G, C> {
+    /// Total amount which flows from the source to the sink
+    #[inline]
+    pub fn value(&self) -> C {
+        self.value
+    }
+
+    /// Node the flow through the edge enters together with its amount. Flow through directed
+    /// edges always enters their `to` end, undirected edges may be used in either direction.
+    /// Self loops and edges which aren't part of the graph have no flow.
+    #[inline]
+    pub fn flow(&self, edge: EdgeId<G>) -> Option<(NodeId<G>, C)> {
+        self.flows.get(&edge).copied()
+    }
+
+    /// Every edge together with the node its flow enters and the amount
+    #[inline]
+    pub fn flows(&self) -> impl Iterator<Item = (EdgeId<G>, NodeId<G>, C)> + '_ {
+        self.flows
+            .iter()
+            .map(|(edge, (node, flow))| (*edge, *node, *flow))
+    }
+
+    /// Nodes on the source side of a minimum cut. The edges leaving this set are saturated and
+    /// their capacities sum up to the flow value.
+    #[inline]
+    pub fn source_side(&self) -> &HashSet<NodeId<G>> {
+        &self.source_side
+    }
+}
+
+impl<G, C: Clone> Clone for MaxFlow<G, C> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            flows: self.flows.clone(),
+            source_side: self.source_side.clone(),
+        }
+    }
+}
+
+impl<G, C: Debug> Debug for MaxFlow<G, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MaxFlow")
+            .field("value", &self.value)
+            .field("flows", &self.flows)
+            .field("source_side", &self.source_side)
+            .finish()
+    }
+}
+
+// residual network over dense node indices. Every edge becomes a pair of arcs `2i` and `2i + 1`
+// which are the reverse of each other, so the reverse of arc `a` is `a ^ 1`.
+struct Residual<G, C> {
+    nodes: Vec<NodeId<G>>,
+    heads: Vec<usize>,
+    residuals: Vec<C>,
+    adjacency: Vec<Vec<usize>>,
+    edges: Vec<(EdgeId<G>, C)>,
+}
+
+impl<G, C: FlowMeasure> Residual<G, C> {
+    fn new<N, E>(graph: &G, mut capacity: impl FnMut(EdgeId<G>, &E) -> C) -> Self
+    where
+        G: GraphRead<N, E>,
+    {
+        let (nodes, indices) = dense_indices(graph);
+
+        let mut residual = Self {
+            heads: Vec::with_capacity(2 * graph.edge_count()),
+            residuals: Vec::with_capacity(2 * graph.edge_count()),
+            adjacency: vec![Vec::new(); nodes.len()],
+            edges: Vec::with_capacity(graph.edge_count()),
+            nodes,
+        };
+        graph.edges().for_each(|edge| {
+            let (Some(ends), Some(weight)) = (graph.edge_ends(edge), graph.edge(edge)) else {
+                return;
+            };
+            let capacity = capacity(edge, weight);
+            let (from, to) = (indices[&ends.from], indices[&ends.to]);
+            // self loops never carry flow anywhere
+            if from == to {
+                return;
+            }
+            // undirected edges can be used in both directions up to their capacity
+            let backwards = if graph.is_directed() {
+                C::default()
+            } else {
+                capacity
+            };
+            let arc = residual.heads.len();
+            residual.heads.extend([to, from]);
+            residual.residuals.extend([capacity, backwards]);
+            residual.adjacency[from].push(arc);
+            residual.adjacency[to].push(arc + 1);
+            residual.edges.push((edge, capacity));
+        });
+        residual
+    }
+
+    #[inline]
+    fn tail(&self, arc: usize) -> usize {
+        self.heads[arc ^ 1]
+    }
+
+    #[inline]
+    fn augment(&mut self, arc: usize, amount: C) {
+        self.residuals[arc] = self.residuals[arc] - amount;
+        self.residuals[arc ^ 1] = self.residuals[arc ^ 1] + amount;
+    }
+
+    // breadth first distances from the source over arcs which still have residual capacity
+    fn levels(&self, source: usize) -> Vec<usize> {
+        let mut levels = vec![usize::MAX; self.nodes.len()];
+        levels[source] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(node) = queue.pop_front() {
+            for &arc in &self.adjacency[node] {
+                let head = self.heads[arc];
+                if levels[head] == usize::MAX && self.residuals[arc] > C::default() {
+                    levels[head] = levels[node] + 1;
+                    queue.push_back(head);
+                }
+            }
+        }
+        levels
+    }
+
+    fn into_max_flow(self, source: usize, value: C) -> MaxFlow<G, C> {
+        let levels = self.levels(source);
+        let source_side = levels
+            .iter()
+            .zip(&self.nodes)
+            .filter(|(level, _)| **level != usize::MAX)
+            .map(|(_, node)| *node)
+            .collect();
+        let flows = self
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(index, (edge, capacity))| {
+                let (from, to) = (self.tail(2 * index), self.heads[2 * index]);
+                let residual = self.residuals[2 * index];
+                // undirected edges carrying flow backwards have more residual than capacity
+                let flow = if residual > *capacity {
+                    (self.nodes[from], residual - *capacity)
+                } else {
+                    (self.nodes[to], *capacity - residual)
+                };
+                (*edge, flow)
+            })
+            .collect();
+
+        MaxFlow {
+            value,
+            flows,
+            source_side,
+        }
+    }
+}
+
+// dense indices of source and sink, if both are distinct nodes of the graph
+fn terminals<G, C>(
+    residual: &Residual<G, C>,
+    source: NodeId<G>,
+    sink: NodeId<G>,
+) -> Option<(usize, usize)> {
+    let source = residual.nodes.iter().position(|node| *node == source)?;
+    let sink = residual.nodes.iter().position(|node| *node == sink)?;
+    (source != sink).then_some((source, sink))
+}
+
+#[inline]
+fn min<C: PartialOrd>(a: C, b: C) -> C {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Computes a maximum flow from the source to the sink by augmenting along shortest paths
+pub fn edmonds_karp<G, N, E, C>(
+    graph: &G,
+    source: NodeId<G>,
+    sink: NodeId<G>,
+    capacity: impl FnMut(EdgeId<G>, &E) -> C,
+) -> MaxFlow<G, C>
+where
+    G: GraphRead<N, E>,
+    C: FlowMeasure,
+{
+    let mut residual = Residual::new(graph, capacity);
+    let mut value = C::default();
+    let Some((source, sink)) = terminals(&residual, source, sink) else {
+        return MaxFlow {
+            value,
+            flows: HashMap::new(),
+            source_side: graph
+                .contains_node(source)
+                .then_some(source)
+                .into_iter()
+                .collect(),
+        };
+    };
+
+    loop {
+        let mut via = vec![None; residual.nodes.len()];
+        let mut queue = VecDeque::from([source]);
+        'search: while let Some(node) = queue.pop_front() {
+            for &arc in &residual.adjacency[node] {
+                let head = residual.heads[arc];
+                if head != source && via[head].is_none() && residual.residuals[arc] > C::default() {
+                    via[head] = Some(arc);
+                    if head == sink {
+                        break 'search;
+                    }
+                    queue.push_back(head);
+                }
+            }
+        }
+        if via[sink].is_none() {
+            break;
+        }
+
+        let path =
+            std::iter::successors(via[sink], |arc| via[residual.tail(*arc)]).collect::<Vec<_>>();
+        let bottleneck = path
+            .iter()
+            .map(|arc| residual.residuals[*arc])
+            .reduce(min)
+            .expect("path has arcs");
+        path.into_iter()
+            .for_each(|arc| residual.augment(arc, bottleneck));
+        value = value + bottleneck;
+    }
+
+    residual.into_max_flow(source, value)
+}
+
+/// Computes a maximum flow from the source to the sink by saturating all shortest paths of the
+/// same length at once, which needs fewer rounds than [`edmonds_karp`]
+pub fn dinic<G, N, E, C>(
+    graph: &G,
+    source: NodeId<G>,
+    sink: NodeId<G>,
+    capacity: impl FnMut(EdgeId<G>, &E) -> C,
+) -> MaxFlow<G, C>
+where
+    G: GraphRead<N, E>,
+    C: FlowMeasure,
+{
+    let mut residual = Residual::new(graph, capacity);
+    let mut value = C::default();
+    let Some((source, sink)) = terminals(&residual, source, sink) else {
+        return MaxFlow {
+            value,
+            flows: HashMap::new(),
+            source_side: graph
+                .contains_node(source)
+                .then_some(source)
+                .into_iter()
+                .collect(),
+        };
+    };
+
+    loop {
+        let mut levels = residual.levels(source);
+        if levels[sink] == usize::MAX {
+            break;
+        }
+
+        // blocking flow: advance along arcs into the next level, retreat from dead ends
+        let mut next_arc = vec![0; residual.nodes.len()];
+        let mut path = Vec::<usize>::new();
+        loop {
+            let node = path.last().map_or(source, |arc| residual.heads[*arc]);
+            if node == sink {
+                let bottleneck = path
+                    .iter()
+                    .map(|arc| residual.residuals[*arc])
+                    .reduce(min)
+                    .expect("path has arcs");
+                path.iter()
+                    .for_each(|arc| residual.augment(*arc, bottleneck));
+                value = value + bottleneck;
+                // continue from the tail of the first saturated arc
+                let saturated = path
+                    .iter()
+                    .position(|arc| residual.residuals[*arc] <= C::default())
+                    .expect("bottleneck arc is saturated");
+                path.truncate(saturated);
+                continue;
+            }
+
+            let admissible = residual.adjacency[node][next_arc[node]..]
+                .iter()
+                .position(|arc| {
+                    residual.residuals[*arc] > C::default()
+                        && levels[residual.heads[*arc]] == levels[node] + 1
+                });
+            match admissible {
+                Some(offset) => {
+                    next_arc[node] += offset;
+                    path.push(residual.adjacency[node][next_arc[node]]);
+                }
+                None => {
+                    next_arc[node] = residual.adjacency[node].len();
+                    levels[node] = usize::MAX;
+                    match path.pop() {
+                        Some(arc) => next_arc[residual.tail(arc)] += 1,
+                        None => break,
+                    }
+                }
+            }
+        }
+    }
+
+    residual.into_max_flow(source, value)
+}
+
+/// Finds a minimum cut of the graph with the Stoer-Wagner algorithm, ignoring edge directions.
+/// Returns the total weight of the cut edges and the nodes on one side of it, or nothing if the
+/// graph has less than two nodes.
+pub fn stoer_wagner<G, N, E, C>(
+    graph: &G,
+    mut weight: impl FnMut(EdgeId<G>, &E) -> C,
+) -> Option<(C, Vec<NodeId<G>>)>
+where
+    G: GraphRead<N, E>,
+    C: Measure,
+{
+    let (nodes, indices) = dense_indices(graph);
+    if nodes.len() < 2 {
+        return None;
+    }
+
+    let count = nodes.len();
+    let mut weights = vec![C::default(); count * count];
+    graph.edges().for_each(|edge| {
+        let (Some(ends), Some(value)) = (graph.edge_ends(edge), graph.edge(edge)) else {
+            return;
+        };
+        let (from, to) = (indices[&ends.from], indices[&ends.to]);
+        if from != to {
+            let value = weight(edge, value);
+            weights[from * count + to] = weights[from * count + to] + value;
+            weights[to * count + from] = weights[to * count + from] + value;
+        }
+    });
+
+    // every merged node stands for a group of original nodes
+    let mut groups = (0..count).map(|index| vec![index]).collect::<Vec<_>>();
+    let mut active = (0..count).collect::<Vec<_>>();
+    let mut best: Option<(C, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        // order the nodes by how tightly they are connected to the ones before them
+        let mut connectivity = vec![C::default(); count];
+        let mut added = vec![false; count];
+        let (mut previous, mut last, mut cut) = (active[0], active[0], C::default());
+        for _ in 0..active.len() {
+            let next = active
+                .iter()
+                .copied()
+                .filter(|node| !added[*node])
+                .reduce(|a, b| {
+                    if connectivity[b] > connectivity[a] {
+                        b
+                    } else {
+                        a
+                    }
+                })
+                .expect("nodes are left");
+            added[next] = true;
+            (previous, last, cut) = (last, next, connectivity[next]);
+            active.iter().for_each(|node| {
+                connectivity[*node] = connectivity[*node] + weights[next * count + node];
+            });
+        }
+
+        // the last node is separated from all others by its connectivity
+        if best.as_ref().is_none_or(|(value, _)| cut < *value) {
+            best = Some((cut, groups[last].clone()));
+        }
+
+        let merged = std::mem::take(&mut groups[last]);
+        groups[previous].extend(merged);
+        active.retain(|node| *node != last);
+        active
+            .iter()
+            .filter(|node| **node != previous)
+            .for_each(|node| {
+                weights[previous * count + node] =
+                    weights[previous * count + node] + weights[last * count + node];
+                weights[node * count + previous] =
+                    weights[node * count + previous] + weights[node * count + last];
+            });
+    }
+
+    best.map(|(value, side)| (value, side.into_iter().map(|index| nodes[index]).collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use crate::storage::edge::EdgeEnds;
+
+    #[test]
+    fn undirected_flow_is_conserved() {
+        let mut graph = Graph::<&str, u32, Undirected>::new();
+        let [s, a, b, t] = ["s", "a", "b", "t"].map(|name| graph.add_node(name));
+        // several edges are stored against the direction the flow has to take
+        for (from, to, capacity) in [(s, a, 3), (b, s, 2), (a, t, 2), (t, b, 3), (b, a, 1)] {
+            graph.add_edge(from, to, capacity);
+        }
+
+        for flow in [
+            edmonds_karp(&graph, s, t, |_, capacity| *capacity),
+            dinic(&graph, s, t, |_, capacity| *capacity),
+        ] {
+            assert_eq!(flow.value(), 5);
+
+            let mut net = HashMap::<_, i64>::new();
+            for (edge, into, amount) in flow.flows() {
+                assert!(amount <= graph[edge]);
+                let ends = graph.edge_ends(edge).unwrap();
+                let out_of = if into == ends.to { ends.from } else { ends.to };
+                *net.entry(into).or_default() += amount as i64;
+                *net.entry(out_of).or_default() -= amount as i64;
+            }
+            assert_eq!(net[&a], 0);
+            assert_eq!(net[&b], 0);
+            assert_eq!(net[&s], -5);
+            assert_eq!(net[&t], 5);
+
+            // the cut around the source is saturated, so the edge stored as b -> s flows into b
+            let edge = graph.edge_between(EdgeEnds::new(b, s)).unwrap();
+            assert_eq!(flow.flow(edge), Some((b, 2)));
+        }
+    }
+}
@@ -0,0 +1,600 @@
+use std::marker::PhantomData;
+
+use crate::storage::{direction::EdgeDirection, edge::EdgeEnds, node::NodeId, traits::GraphRead};
+
+use super::dense_indices;
+
+/// Pairs of pattern and target nodes which are mapped onto each other
+pub type Mapping<P, T> = Vec<(NodeId<P>, NodeId<T>)>;
+
+/// Checks whether both graphs have the same structure, ignoring weights
+pub fn is_isomorphic<P, T, NP, EP, NT, ET>(a: &P, b: &T) -> bool
+where
+    P: GraphRead<NP, EP>,
+    T: GraphRead<NT, ET>,
+{
+    isomorphisms(a, b).next().is_some()
+}
+
+/// Checks whether both graphs have the same structure with matching node and edge weights
+pub fn is_isomorphic_matching<P, T, NP, EP, NT, ET>(
+    a: &P,
+    b: &T,
+    node_match: impl FnMut(&NP, &NT) -> bool,
+    edge_match: impl FnMut(&EP, &ET) -> bool,
+) -> bool
+where
+    P: GraphRead<NP, EP>,
+    T: GraphRead<NT, ET>,
+{
+    isomorphisms_matching(a, b, node_match, edge_match)
+        .next()
+        .is_some()
+}
+
+/// All ways to map the nodes of one graph onto the nodes of another with the same structure,
+/// ignoring weights
+pub fn isomorphisms<'a, P, T, NP, EP, NT, ET>(
+    a: &'a P,
+    b: &'a T,
+) -> impl Iterator<Item = Mapping<P, T>> + 'a
+where
+    P: GraphRead<NP, EP>,
+    T: GraphRead<NT, ET>,
+    NP: 'a,
+    EP: 'a,
+    NT: 'a,
+    ET: 'a,
+{
+    Vf2::new(a, b, true, |_: &NP, _: &NT| true, |_: &EP, _: &ET| true)
+}
+
+/// All ways to map the nodes of one graph onto the nodes of another with the same structure and
+/// matching node and edge weights
+pub fn isomorphisms_matching<'a, P, T, NP, EP, NT, ET>(
+    a: &'a P,
+    b: &'a T,
+    node_match: impl FnMut(&NP, &NT) -> bool + 'a,
+    edge_match: impl FnMut(&EP, &ET) -> bool + 'a,
+) -> impl Iterator<Item = Mapping<P, T>> + 'a
+where
+    P: GraphRead<NP, EP>,
+    T: GraphRead<NT, ET>,
+    NP: 'a,
+    EP: 'a,
+    NT: 'a,
+    ET: 'a,
+{
+    Vf2::new(a, b, true, node_match, edge_match)
+}
+
+/// All ways to find the pattern in the target, ignoring weights. Every pattern edge needs a
+/// counterpart in the target, while the target may have additional edges between mapped nodes.
+pub fn subgraph_isomorphisms<'a, P, T, NP, EP, NT, ET>(
+    pattern: &'a P,
+    target: &'a T,
+) -> impl Iterator<Item = Mapping<P, T>> + 'a
+where
+    P: GraphRead<NP, EP>,
+    T: GraphRead<NT, ET>,
+    NP: 'a,
+    EP: 'a,
+    NT: 'a,
+    ET: 'a,
+{
+    Vf2::new(
+        pattern,
+        target,
+        false,
+        |_: &NP, _: &NT| true,
+        |_: &EP, _: &ET| true,
+    )
+}
+
+/// All ways to find the pattern in the target with matching node and edge weights. Every pattern
+/// edge needs a counterpart in the target, while the target may have additional edges between
+/// mapped nodes.
+pub fn subgraph_isomorphisms_matching<'a, P, T, NP, EP, NT, ET>(
+    pattern: &'a P,
+    target: &'a T,
+    node_match: impl FnMut(&NP, &NT) -> bool + 'a,
+    edge_match: impl FnMut(&EP, &ET) -> bool + 'a,
+) -> impl Iterator<Item = Mapping<P, T>> + 'a
+where
+    P: GraphRead<NP, EP>,
+    T: GraphRead<NT, ET>,
+    NP: 'a,
+    EP: 'a,
+    NT: 'a,
+    ET: 'a,
+{
+    Vf2::new(pattern, target, false, node_match, edge_match)
+}
+
+const UNMAPPED: usize = usize::MAX;
+
+// one graph of the search state, with nodes addressed by dense indices
+struct Side<G> {
+    nodes: Vec<NodeId<G>>,
+    // distinct neighbors without the node itself. Both are the same for undirected graphs.
+    outgoing: Vec<Vec<usize>>,
+    incoming: Vec<Vec<usize>>,
+    neighbors: Vec<Vec<usize>>,
+    core: Vec<usize>,
+    // depth at which a node was mapped or became adjacent to a mapped node, zero otherwise
+    terminal: Vec<usize>,
+    // number of unmapped nodes which are adjacent to mapped ones
+    terminal_len: usize,
+}
+
+impl<G> Side<G> {
+    fn new<N, E>(graph: &G) -> Self
+    where
+        G: GraphRead<N, E>,
+    {
+        let (nodes, indices) = dense_indices(graph);
+        let distinct = |index: usize, adjacent: &mut dyn Iterator<Item = NodeId<G>>| {
+            let mut adjacent = adjacent
+                .map(|node| indices[&node])
+                .filter(|other| *other != index)
+                .collect::<Vec<_>>();
+            adjacent.sort_unstable();
+            adjacent.dedup();
+            adjacent
+        };
+
+        let outgoing = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| distinct(index, &mut graph.outcoming_nodes(*node)))
+            .collect::<Vec<_>>();
+        let incoming = nodes
+            .iter()
+            .enumerate()
+            .map(|(index, node)| distinct(index, &mut graph.incoming_nodes(*node)))
+            .collect::<Vec<_>>();
+        let neighbors = outgoing
+            .iter()
+            .zip(&incoming)
+            .map(|(outgoing, incoming)| {
+                let mut neighbors = outgoing.iter().chain(incoming).copied().collect::<Vec<_>>();
+                neighbors.sort_unstable();
+                neighbors.dedup();
+                neighbors
+            })
+            .collect();
+
+        Self {
+            core: vec![UNMAPPED; nodes.len()],
+            terminal: vec![0; nodes.len()],
+            terminal_len: 0,
+            nodes,
+            outgoing,
+            incoming,
+            neighbors,
+        }
+    }
+
+    #[inline]
+    fn is_mapped(&self, node: usize) -> bool {
+        self.core[node] != UNMAPPED
+    }
+
+    #[inline]
+    fn is_terminal(&self, node: usize) -> bool {
+        self.terminal[node] != 0 && !self.is_mapped(node)
+    }
+
+    fn map(&mut self, node: usize, other: usize, depth: usize) {
+        if self.terminal[node] != 0 {
+            self.terminal_len -= 1;
+        } else {
+            self.terminal[node] = depth;
+        }
+        self.core[node] = other;
+        for &neighbor in &self.neighbors[node] {
+            if self.terminal[neighbor] == 0 {
+                self.terminal[neighbor] = depth;
+                if !self.is_mapped(neighbor) {
+                    self.terminal_len += 1;
+                }
+            }
+        }
+    }
+
+    fn unmap(&mut self, node: usize, depth: usize) {
+        for &neighbor in &self.neighbors[node] {
+            if self.terminal[neighbor] == depth {
+                self.terminal[neighbor] = 0;
+                if !self.is_mapped(neighbor) {
+                    self.terminal_len -= 1;
+                }
+            }
+        }
+        self.core[node] = UNMAPPED;
+        if self.terminal[node] == depth {
+            self.terminal[node] = 0;
+        } else {
+            self.terminal_len += 1;
+        }
+    }
+
+    // unmapped neighbors which are adjacent to mapped nodes and those which aren't
+    fn lookahead(&self, node: usize) -> (usize, usize) {
+        self.neighbors[node]
+            .iter()
+            .filter(|neighbor| !self.is_mapped(**neighbor))
+            .fold((0, 0), |(terminal, new), neighbor| {
+                if self.terminal[*neighbor] != 0 {
+                    (terminal + 1, new)
+                } else {
+                    (terminal, new + 1)
+                }
+            })
+    }
+}
+
+// pattern node whose candidates are tried at one depth of the search
+#[derive(Clone, Copy)]
+struct Frame {
+    node: usize,
+    next: usize,
+    current: Option<usize>,
+    terminal: bool,
+}
+
+// depth first search over partial mappings, extending them by one pair of nodes at a time
+struct Vf2<'a, P, T, NP, EP, NT, ET, NM, EM> {
+    pattern: &'a P,
+    target: &'a T,
+    p: Side<P>,
+    t: Side<T>,
+    // isomorphism if set, otherwise the target may have extra edges
+    exact: bool,
+    node_match: NM,
+    edge_match: EM,
+    stack: Vec<Frame>,
+    started: bool,
+    #[allow(clippy::type_complexity)]
+    _weights: PhantomData<fn() -> (NP, EP, NT, ET)>,
+}
+
+impl<'a, P, T, NP, EP, NT, ET, NM, EM> Vf2<'a, P, T, NP, EP, NT, ET, NM, EM>
+where
+    P: GraphRead<NP, EP>,
+    T: GraphRead<NT, ET>,
+    NM: FnMut(&NP, &NT) -> bool,
+    EM: FnMut(&EP, &ET) -> bool,
+{
+    fn new(pattern: &'a P, target: &'a T, exact: bool, node_match: NM, edge_match: EM) -> Self {
+        Self {
+            pattern,
+            target,
+            p: Side::new(pattern),
+            t: Side::new(target),
+            exact,
+            node_match,
+            edge_match,
+            stack: Vec::new(),
+            started: false,
+            _weights: PhantomData,
+        }
+    }
+
+    fn plausible(&self) -> bool {
+        let (pattern, target) = (self.pattern, self.target);
+        if P::Direction::DIRECTED != T::Direction::DIRECTED {
+            return false;
+        }
+        if self.exact {
+            pattern.node_count() == target.node_count()
+                && pattern.edge_count() == target.edge_count()
+        } else {
+            pattern.node_count() <= target.node_count()
+                && pattern.edge_count() <= target.edge_count()
+        }
+    }
+
+    fn open_frame(&mut self) {
+        let terminal = self.p.terminal_len > 0;
+        let node = (0..self.p.nodes.len())
+            .find(|node| !self.p.is_mapped(*node) && (!terminal || self.p.is_terminal(*node)))
+            .expect("pattern has unmapped nodes");
+        self.stack.push(Frame {
+            node,
+            next: 0,
+            current: None,
+            terminal,
+        });
+    }
+
+    // every edge from `p_from` to `p_to` needs its own matching edge from `t_from` to `t_to`
+    fn edges_match(&mut self, p_from: usize, p_to: usize, t_from: usize, t_to: usize) -> bool {
+        let (pattern, target) = (self.pattern, self.target);
+        let p_edges = pattern
+            .edges_between(EdgeEnds::new(self.p.nodes[p_from], self.p.nodes[p_to]))
+            .filter_map(|edge| pattern.edge(edge))
+            .collect::<Vec<_>>();
+        let t_edges = target
+            .edges_between(EdgeEnds::new(self.t.nodes[t_from], self.t.nodes[t_to]))
+            .filter_map(|edge| target.edge(edge))
+            .collect::<Vec<_>>();
+
+        if (self.exact && p_edges.len() != t_edges.len()) || p_edges.len() > t_edges.len() {
+            return false;
+        }
+        let allowed = p_edges
+            .iter()
+            .map(|p_edge| {
+                (0..t_edges.len())
+                    .filter(|t_edge| (self.edge_match)(p_edge, t_edges[*t_edge]))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let mut owners = vec![UNMAPPED; t_edges.len()];
+        (0..p_edges.len()).all(|p_edge| {
+            augment_edges(
+                p_edge,
+                &allowed,
+                &mut owners,
+                &mut vec![false; t_edges.len()],
+            )
+        })
+    }
+
+    fn feasible(&mut self, p: usize, t: usize) -> bool {
+        let (Some(p_weight), Some(t_weight)) = (
+            self.pattern.node(self.p.nodes[p]),
+            self.target.node(self.t.nodes[t]),
+        ) else {
+            return false;
+        };
+        if !(self.node_match)(p_weight, t_weight) || !self.edges_match(p, p, t, t) {
+            return false;
+        }
+
+        // edges to mapped nodes need counterparts, in both directions for isomorphisms
+        for index in 0..self.p.outgoing[p].len() {
+            let neighbor = self.p.outgoing[p][index];
+            if self.p.is_mapped(neighbor)
+                && !self.edges_match(p, neighbor, t, self.p.core[neighbor])
+            {
+                return false;
+            }
+        }
+        if P::Direction::DIRECTED {
+            for index in 0..self.p.incoming[p].len() {
+                let neighbor = self.p.incoming[p][index];
+                if self.p.is_mapped(neighbor)
+                    && !self.edges_match(neighbor, p, self.p.core[neighbor], t)
+                {
+                    return false;
+                }
+            }
+        }
+        if self.exact {
+            let outgoing = self.t.outgoing[t]
+                .iter()
+                .filter(|neighbor| self.t.is_mapped(**neighbor))
+                .all(|neighbor| {
+                    self.p.outgoing[p]
+                        .binary_search(&self.t.core[*neighbor])
+                        .is_ok()
+                });
+            let incoming = self.t.incoming[t]
+                .iter()
+                .filter(|neighbor| self.t.is_mapped(**neighbor))
+                .all(|neighbor| {
+                    self.p.incoming[p]
+                        .binary_search(&self.t.core[*neighbor])
+                        .is_ok()
+                });
+            if !outgoing || !incoming {
+                return false;
+            }
+        }
+
+        // neighbors which still have to be mapped need enough room on the target side
+        let (p_terminal, p_new) = self.p.lookahead(p);
+        let (t_terminal, t_new) = self.t.lookahead(t);
+        if self.exact {
+            p_terminal == t_terminal && p_new == t_new
+        } else {
+            p_terminal <= t_terminal && p_terminal + p_new <= t_terminal + t_new
+        }
+    }
+
+    fn mapping(&self) -> Mapping<P, T> {
+        (0..self.p.nodes.len())
+            .map(|p| (self.p.nodes[p], self.t.nodes[self.p.core[p]]))
+            .collect()
+    }
+}
+
+impl<P, T, NP, EP, NT, ET, NM, EM> Iterator for Vf2<'_, P, T, NP, EP, NT, ET, NM, EM>
+where
+    P: GraphRead<NP, EP>,
+    T: GraphRead<NT, ET>,
+    NM: FnMut(&NP, &NT) -> bool,
+    EM: FnMut(&EP, &ET) -> bool,
+{
+    type Item = Mapping<P, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if !self.plausible() {
+                return None;
+            }
+            if self.p.nodes.is_empty() {
+                return Some(Vec::new());
+            }
+            self.open_frame();
+        }
+
+        loop {
+            let depth = self.stack.len();
+            let Frame {
+                node,
+                next,
+                current,
+                terminal,
+            } = *self.stack.last()?;
+            if let Some(current) = current {
+                self.p.unmap(node, depth);
+                self.t.unmap(current, depth);
+            }
+
+            // pattern nodes next to mapped ones have to be mapped next to them as well
+            let candidate = (next..self.t.nodes.len()).find(|t| {
+                !self.t.is_mapped(*t)
+                    && (terminal == self.t.is_terminal(*t) || !(terminal || self.exact))
+                    && self.feasible(node, *t)
+            });
+            let frame = self.stack.last_mut().expect("frame is on the stack");
+            frame.current = candidate;
+            let Some(candidate) = candidate else {
+                self.stack.pop();
+                continue;
+            };
+            frame.next = candidate + 1;
+
+            self.p.map(node, candidate, depth);
+            self.t.map(candidate, node, depth);
+            if depth == self.p.nodes.len() {
+                return Some(self.mapping());
+            }
+            self.open_frame();
+        }
+    }
+}
+
+// Kuhn's augmenting path step for matching parallel pattern edges to distinct target edges
+fn augment_edges(
+    p_edge: usize,
+    allowed: &[Vec<usize>],
+    owners: &mut [usize],
+    seen: &mut [bool],
+) -> bool {
+    for &t_edge in &allowed[p_edge] {
+        if seen[t_edge] {
+            continue;
+        }
+        seen[t_edge] = true;
+        if owners[t_edge] == UNMAPPED || augment_edges(owners[t_edge], allowed, owners, seen) {
+            owners[t_edge] = p_edge;
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    fn graph(nodes: usize, edges: &[(usize, usize)]) -> Graph<usize, ()> {
+        let mut graph = Graph::multigraph();
+        let ids = (0..nodes)
+            .map(|node| graph.add_node(node))
+            .collect::<Vec<_>>();
+        for (from, to) in edges {
+            graph.add_edge(ids[*from], ids[*to], ()).unwrap();
+        }
+        graph
+    }
+
+    fn sorted<P, T>(
+        mappings: impl Iterator<Item = Mapping<P, T>>,
+        pattern: &P,
+        target: &T,
+    ) -> Vec<Vec<(usize, usize)>>
+    where
+        P: GraphRead<usize, ()>,
+        T: GraphRead<usize, ()>,
+    {
+        let mut mappings = mappings
+            .map(|mapping| {
+                let mut mapping = mapping
+                    .into_iter()
+                    .map(|(a, b)| (*pattern.node(a).unwrap(), *target.node(b).unwrap()))
+                    .collect::<Vec<_>>();
+                mapping.sort_unstable();
+                mapping
+            })
+            .collect::<Vec<_>>();
+        mappings.sort_unstable();
+        mappings
+    }
+
+    #[test]
+    fn directed_cycle() {
+        let cycle = graph(3, &[(0, 1), (1, 2), (2, 0)]);
+        let reversed = graph(3, &[(1, 0), (2, 1), (0, 2)]);
+        let path = graph(3, &[(0, 1), (1, 2), (0, 2)]);
+        assert!(is_isomorphic(&cycle, &reversed));
+        assert!(!is_isomorphic(&cycle, &path));
+        // the rotations are the only automorphisms
+        assert_eq!(
+            sorted(isomorphisms(&cycle, &cycle), &cycle, &cycle),
+            [
+                [(0, 0), (1, 1), (2, 2)],
+                [(0, 1), (1, 2), (2, 0)],
+                [(0, 2), (1, 0), (2, 1)],
+            ]
+        );
+    }
+
+    #[test]
+    fn parallel_edges_need_the_same_multiplicity() {
+        let first_doubled = graph(3, &[(0, 1), (0, 1), (1, 2)]);
+        let second_doubled = graph(3, &[(0, 1), (1, 2), (1, 2)]);
+        assert!(is_isomorphic(&first_doubled, &first_doubled));
+        assert!(!is_isomorphic(&first_doubled, &second_doubled));
+        assert_eq!(isomorphisms(&first_doubled, &first_doubled).count(), 1);
+    }
+
+    #[test]
+    fn subgraph_with_parallel_edges() {
+        let double = graph(2, &[(0, 1), (0, 1)]);
+        let single = graph(2, &[(0, 1)]);
+        let target = graph(3, &[(0, 1), (1, 2), (1, 2)]);
+
+        // the target may have more parallel edges than the pattern, but not fewer
+        assert_eq!(
+            sorted(subgraph_isomorphisms(&single, &target), &single, &target),
+            [[(0, 0), (1, 1)], [(0, 1), (1, 2)]]
+        );
+        assert_eq!(
+            sorted(subgraph_isomorphisms(&double, &target), &double, &target),
+            [[(0, 1), (1, 2)]]
+        );
+        assert_eq!(subgraph_isomorphisms(&double, &single).count(), 0);
+    }
+
+    #[test]
+    fn matching_weights() {
+        let mut a = Graph::<&str, u32, Undirected>::new();
+        let [x, y, z] = ["x", "y", "z"].map(|name| a.add_node(name));
+        a.add_edge(x, y, 1);
+        a.add_edge(y, z, 2);
+        let mut b = Graph::<&str, u32, Undirected>::new();
+        let [x, y, z] = ["x", "y", "z"].map(|name| b.add_node(name));
+        b.add_edge(z, y, 1);
+        b.add_edge(y, x, 2);
+
+        assert!(is_isomorphic(&a, &b));
+        assert!(!is_isomorphic_matching(
+            &a,
+            &b,
+            |a, b| a == b,
+            |a, b| a == b
+        ));
+        assert!(is_isomorphic_matching(&a, &b, |_, _| true, |a, b| a == b));
+        assert_eq!(
+            isomorphisms_matching(&a, &b, |a, b| a == b, |_, _| true).count(),
+            1
+        );
+    }
+}
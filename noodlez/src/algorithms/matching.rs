@@ -0,0 +1,320 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ops::Neg;
+
+use crate::storage::{edge::EdgeId, node::NodeId, traits::GraphRead};
+
+use super::{measure::FlowMeasure, opposite, positions};
+
+/// Splits the nodes into two sides so that every edge connects both sides, ignoring edge
+/// directions. Fails if the graph contains an odd cycle, which includes self loops.
+#[allow(clippy::type_complexity)]
+pub fn is_bipartite<G, N, E>(graph: &G) -> Option<(Vec<NodeId<G>>, Vec<NodeId<G>>)>
+where
+    G: GraphRead<N, E>,
+{
+    let mut sides = HashMap::with_capacity(graph.node_count());
+    for start in graph.nodes() {
+        if sides.contains_key(&start) {
+            continue;
+        }
+        sides.insert(start, false);
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            let side = sides[&node];
+            for neighbor in graph.neighbor_nodes(node) {
+                match sides.get(&neighbor) {
+                    Some(other) if *other == side => return None,
+                    Some(_) => {}
+                    None => {
+                        sides.insert(neighbor, !side);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+    }
+
+    Some(graph.nodes().partition(|node| !sides[node]))
+}
+
+/// Finds a maximum matching of a bipartite graph with the Hopcroft-Karp algorithm, ignoring edge
+/// directions. Returns the matched edges or nothing if the graph isn't bipartite.
+pub fn hopcroft_karp<G, N, E>(graph: &G) -> Option<Vec<EdgeId<G>>>
+where
+    G: GraphRead<N, E>,
+{
+    let (left, right) = is_bipartite(graph)?;
+    let right_indices = positions(&right);
+    let adjacency = left
+        .iter()
+        .map(|node| {
+            graph
+                .neighbor_edges(*node)
+                .filter_map(|edge| Some((right_indices[&opposite(graph, edge, *node)?], edge)))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut left_mates = vec![None; left.len()];
+    let mut right_mates = vec![None; right.len()];
+    let mut distances = vec![usize::MAX; left.len()];
+    loop {
+        // layer the left nodes by the length of alternating paths from the free ones
+        let mut queue = VecDeque::new();
+        for (node, mate) in left_mates.iter().enumerate() {
+            distances[node] = if mate.is_none() {
+                queue.push_back(node);
+                0
+            } else {
+                usize::MAX
+            };
+        }
+        let mut found = false;
+        while let Some(node) = queue.pop_front() {
+            for (right, _) in &adjacency[node] {
+                match right_mates[*right] {
+                    None => found = true,
+                    Some(mate) if distances[mate] == usize::MAX => {
+                        distances[mate] = distances[node] + 1;
+                        queue.push_back(mate);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+        if !found {
+            break;
+        }
+
+        // augment along vertex disjoint shortest alternating paths
+        let mut next_edge = vec![0; left.len()];
+        for start in 0..left.len() {
+            if left_mates[start].is_some() {
+                continue;
+            }
+            let mut stack = vec![start];
+            while let Some(&node) = stack.last() {
+                let Some(&(right, _)) = adjacency[node].get(next_edge[node]) else {
+                    distances[node] = usize::MAX;
+                    stack.pop();
+                    continue;
+                };
+                next_edge[node] += 1;
+                match right_mates[right] {
+                    None => {
+                        // every node on the stack gets matched along the edge it took last
+                        stack.into_iter().for_each(|node| {
+                            let (right, edge) = adjacency[node][next_edge[node] - 1];
+                            left_mates[node] = Some(edge);
+                            right_mates[right] = Some(node);
+                        });
+                        break;
+                    }
+                    Some(mate) if distances[mate] == distances[node] + 1 => stack.push(mate),
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    Some(left_mates.into_iter().flatten().collect())
+}
+
+/// Assigns every given node to a distinct neighbor outside of them with the Hungarian algorithm,
+/// so that the summed weight of the used edges is minimal. Negate the weights to maximize instead.
+///
+/// Returns the total weight and the used edges, or nothing if no such assignment exists. Edges
+/// between two of the given nodes are ignored, as are edge directions. Weights have to be signed
+/// since the intermediate potentials of the algorithm can turn negative.
+pub fn hungarian<G, N, E, C>(
+    graph: &G,
+    nodes: impl IntoIterator<Item = NodeId<G>>,
+    mut weight: impl FnMut(EdgeId<G>, &E) -> C,
+) -> Option<(C, Vec<EdgeId<G>>)>
+where
+    G: GraphRead<N, E>,
+    C: FlowMeasure + Neg<Output = C>,
+{
+    let mut row_set = HashSet::new();
+    let rows = nodes
+        .into_iter()
+        .filter(|node| graph.contains_node(*node) && row_set.insert(*node))
+        .collect::<Vec<_>>();
+
+    let mut columns = HashMap::new();
+    let mut costs = Vec::<HashMap<usize, (C, EdgeId<G>)>>::with_capacity(rows.len());
+    for row in &rows {
+        let mut row_costs = HashMap::new();
+        for edge in graph.neighbor_edges(*row) {
+            let (Some(column), Some(value)) = (opposite(graph, edge, *row), graph.edge(edge))
+            else {
+                continue;
+            };
+            if row_set.contains(&column) {
+                continue;
+            }
+            let next = columns.len();
+            let column = *columns.entry(column).or_insert(next);
+            let cost = weight(edge, value);
+            // parallel edges only matter through the cheapest one
+            if row_costs
+                .get(&column)
+                .is_none_or(|(best, _): &(C, _)| cost < *best)
+            {
+                row_costs.insert(column, (cost, edge));
+            }
+        }
+        costs.push(row_costs);
+    }
+    if rows.len() > columns.len() {
+        return None;
+    }
+
+    // potentials and matches use 1-based indices, the extra column 0 holds the row being added
+    let zero = C::default();
+    let (row_count, column_count) = (rows.len(), columns.len());
+    let mut row_potentials = vec![zero; row_count + 1];
+    let mut column_potentials = vec![zero; column_count + 1];
+    let mut column_rows = vec![0; column_count + 1];
+    let mut way = vec![0; column_count + 1];
+    for row in 1..=row_count {
+        column_rows[0] = row;
+        let mut column = 0;
+        // `None` stands for an infinite slack, i.e. no edge into the column yet
+        let mut slack = vec![None::<C>; column_count + 1];
+        let mut used = vec![false; column_count + 1];
+        loop {
+            used[column] = true;
+            let current_row = column_rows[column];
+            let mut delta = None::<C>;
+            let mut next_column = 0;
+            for other in 1..=column_count {
+                if used[other] {
+                    continue;
+                }
+                if let Some((cost, _)) = costs[current_row - 1].get(&(other - 1)) {
+                    let reduced = *cost - row_potentials[current_row] - column_potentials[other];
+                    if slack[other].is_none_or(|slack| reduced < slack) {
+                        slack[other] = Some(reduced);
+                        way[other] = column;
+                    }
+                }
+                if let Some(slack) = slack[other] {
+                    if delta.is_none_or(|delta| slack < delta) {
+                        delta = Some(slack);
+                        next_column = other;
+                    }
+                }
+            }
+            // no column is reachable from the rows visited so far
+            let delta = delta?;
+
+            for other in 0..=column_count {
+                if used[other] {
+                    let row = column_rows[other];
+                    row_potentials[row] = row_potentials[row] + delta;
+                    column_potentials[other] = column_potentials[other] - delta;
+                } else if let Some(slack) = slack[other].as_mut() {
+                    *slack = *slack - delta;
+                }
+            }
+            column = next_column;
+            if column_rows[column] == 0 {
+                break;
+            }
+        }
+        // flip the matches along the augmenting path
+        while column != 0 {
+            let previous = way[column];
+            column_rows[column] = column_rows[previous];
+            column = previous;
+        }
+    }
+
+    let mut total = zero;
+    let mut edges = Vec::with_capacity(row_count);
+    for column in 1..=column_count {
+        let row = column_rows[column];
+        if row != 0 {
+            let (cost, edge) = costs[row - 1][&(column - 1)];
+            total = total + cost;
+            edges.push(edge);
+        }
+    }
+    Some((total, edges))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn bipartite_sides() {
+        let mut graph = Graph::<u32, (), Undirected>::new();
+        let nodes = (0..4).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        for (from, to) in [(0, 1), (1, 2), (2, 3), (3, 0)] {
+            graph.add_edge(nodes[from], nodes[to], ());
+        }
+        let (left, right) = is_bipartite(&graph).unwrap();
+        assert_eq!(left, [nodes[0], nodes[2]]);
+        assert_eq!(right, [nodes[1], nodes[3]]);
+
+        // closing a triangle makes an odd cycle
+        graph.add_edge(nodes[0], nodes[2], ());
+        assert!(is_bipartite(&graph).is_none());
+    }
+
+    #[test]
+    fn hopcroft_karp_on_a_path() {
+        let mut graph = Graph::<u32, ()>::new();
+        let nodes = (0..4).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        let edges = [(0, 1), (1, 2), (2, 3)]
+            .map(|(from, to)| graph.add_edge(nodes[from], nodes[to], ()).unwrap());
+
+        // matching the middle edge first would leave both ends unmatched
+        let matching = hopcroft_karp(&graph)
+            .unwrap()
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(matching, HashSet::from([edges[0], edges[2]]));
+    }
+
+    #[test]
+    fn hungarian_assignment() {
+        let mut graph = Graph::<&str, i32, Undirected>::multigraph();
+        let [worker_a, worker_b, job_a, job_b] =
+            ["wa", "wb", "ja", "jb"].map(|name| graph.add_node(name));
+        graph.add_edge(worker_a, job_a, 4);
+        let cheap = graph.add_edge(worker_a, job_b, 1).unwrap();
+        graph.add_edge(worker_b, job_a, 3);
+        // only the cheaper one of parallel edges counts
+        let parallel = graph.add_edge(worker_b, job_a, 2).unwrap();
+        graph.add_edge(worker_b, job_b, 5);
+
+        let (total, edges) = hungarian(&graph, [worker_a, worker_b], |_, cost| *cost).unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(
+            edges.into_iter().collect::<HashSet<_>>(),
+            HashSet::from([cheap, parallel])
+        );
+
+        // negated weights pick the most expensive assignment instead
+        let (total, _) = hungarian(&graph, [worker_a, worker_b], |_, cost| -*cost).unwrap();
+        assert_eq!(total, -9);
+    }
+
+    #[test]
+    fn hungarian_without_assignment() {
+        let mut graph = Graph::<&str, i32, Undirected>::new();
+        let [worker_a, worker_b, job_a, job_b] =
+            ["wa", "wb", "ja", "jb"].map(|name| graph.add_node(name));
+        graph.add_edge(worker_a, job_a, 1);
+        graph.add_edge(worker_b, job_a, 1);
+
+        // both workers compete for the same job although there are enough jobs
+        assert!(hungarian(&graph, [worker_a, worker_b], |_, cost| *cost).is_none());
+        // a worker without any edge can't be assigned either
+        assert!(hungarian(&graph, [worker_a, job_b], |_, cost| *cost).is_none());
+    }
+}
@@ -0,0 +1,47 @@
+use std::cmp::Ordering;
+use std::ops::{Add, Sub};
+
+/// Cost of an edge or a path which can be summed up and compared. `Default` is the zero cost.
+pub trait Measure: Copy + PartialOrd + Add<Output = Self> + Default {}
+
+impl<T> Measure for T where T: Copy + PartialOrd + Add<Output = T> + Default {}
+
+/// Capacity of an edge which can be pushed through in parts, leaving the difference behind
+pub trait FlowMeasure: Measure + Sub<Output = Self> {}
+
+impl<T> FlowMeasure for T where T: Measure + Sub<Output = T> {}
+
+// binary heap entry which pops the smallest cost first. Incomparable costs (NaN) are popped last.
+pub(crate) struct MinScored<C, T>(pub C, pub T);
+
+impl<C: PartialOrd, T> PartialEq for MinScored<C, T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<C: PartialOrd, T> Eq for MinScored<C, T> {}
+
+impl<C: PartialOrd, T> PartialOrd for MinScored<C, T> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C: PartialOrd, T> Ord for MinScored<C, T> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        #[allow(clippy::eq_op)]
+        let is_nan = |c: &C| c != c;
+        match other.0.partial_cmp(&self.0) {
+            Some(ordering) => ordering,
+            None => match (is_nan(&self.0), is_nan(&other.0)) {
+                (true, false) => Ordering::Less,
+                (false, true) => Ordering::Greater,
+                _ => Ordering::Equal,
+            },
+        }
+    }
+}
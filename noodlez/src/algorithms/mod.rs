@@ -0,0 +1,122 @@
+pub mod centrality;
+pub mod cliques;
+pub mod community;
+pub mod components;
+pub mod dominators;
+pub mod flow;
+pub mod isomorphism;
+pub mod matching;
+pub mod measure;
+pub mod scc;
+pub mod shortest_path;
+pub mod spanning_tree;
+pub mod toposort;
+pub mod transitive;
+pub mod traversal;
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::storage::{edge::EdgeId, node::NodeId, traits::GraphRead};
+
+// node on the other end of an edge which is adjacent to `node`
+#[inline]
+pub(crate) fn opposite<N, E, G: GraphRead<N, E>>(
+    graph: &G,
+    edge: EdgeId<G>,
+    node: NodeId<G>,
+) -> Option<NodeId<G>> {
+    graph.edge_ends(edge).map(|ends| {
+        if ends.from == node {
+            ends.to
+        } else {
+            ends.from
+        }
+    })
+}
+
+// nodes in graph order, and the index of every node in that order
+#[inline]
+pub(crate) fn dense_indices<N, E, G: GraphRead<N, E>>(
+    graph: &G,
+) -> (Vec<NodeId<G>>, HashMap<NodeId<G>, usize>) {
+    let nodes = graph.nodes().collect::<Vec<_>>();
+    let indices = positions(&nodes);
+    (nodes, indices)
+}
+
+// index of every node in the slice
+#[inline]
+pub(crate) fn positions<G>(nodes: &[NodeId<G>]) -> HashMap<NodeId<G>, usize> {
+    nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (*node, index))
+        .collect()
+}
+
+// every edge as an arc with its cost, undirected edges go both ways unless they are self loops
+#[allow(clippy::type_complexity)]
+pub(crate) fn arcs<G, N, E, C>(
+    graph: &G,
+    mut cost: impl FnMut(EdgeId<G>, &E) -> C,
+) -> Vec<(NodeId<G>, NodeId<G>, EdgeId<G>, C)>
+where
+    G: GraphRead<N, E>,
+    C: Copy,
+{
+    let mut arcs = Vec::with_capacity(graph.edge_count());
+    graph.edges().for_each(|edge| {
+        let (Some(ends), Some(weight)) = (graph.edge_ends(edge), graph.edge(edge)) else {
+            return;
+        };
+        let cost = cost(edge, weight);
+        arcs.push((ends.from, ends.to, edge, cost));
+        if !graph.is_directed() && ends.from != ends.to {
+            arcs.push((ends.to, ends.from, edge, cost));
+        }
+    });
+    arcs
+}
+
+/// Cycle of nodes in a graph, e.g. one which prevents it from being sorted topologically
+///
+/// Every node of the cycle has an edge to its successor and the last one has an edge to the
+/// first one.
+pub struct Cycle<G> {
+    nodes: Vec<NodeId<G>>,
+}
+
+impl<G> Cycle<G> {
+    #[inline]
+    pub fn nodes(&self) -> &[NodeId<G>] {
+        &self.nodes
+    }
+
+    #[inline]
+    pub fn into_nodes(self) -> Vec<NodeId<G>> {
+        self.nodes
+    }
+}
+
+impl<G> Clone for Cycle<G> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+        }
+    }
+}
+
+impl<G> Debug for Cycle<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Cycle").field(&self.nodes).finish()
+    }
+}
+
+impl<G> PartialEq for Cycle<G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes
+    }
+}
+
+impl<G> Eq for Cycle<G> {}
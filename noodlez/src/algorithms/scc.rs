@@ -0,0 +1,167 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::storage::{
+    graph::slotmap_graph::Graph,
+    node::NodeId,
+    traits::{GraphRead, GraphWrite},
+};
+
+use super::traversal::{depth_first_search, Control, DfsEvent};
+
+/// Graph whose nodes are the strongly connected components of another graph
+pub type Condensation<N, E> = Graph<Vec<N>, E>;
+
+/// Finds the strongly connected components with Tarjan's algorithm. The components are returned
+/// in reverse topological order, i.e. no component has an edge into a later one.
+pub fn tarjan_scc<G, N, E>(graph: &G) -> Vec<Vec<NodeId<G>>>
+where
+    G: GraphRead<N, E>,
+{
+    let mut indices = HashMap::new();
+    let mut low_links = HashMap::<NodeId<G>, usize>::new();
+    let mut parents = HashMap::new();
+    let mut stack = Vec::new();
+    let mut on_stack = HashSet::new();
+    let mut components = Vec::new();
+
+    depth_first_search::<_, _, _, ()>(graph, graph.nodes(), |event| {
+        match event {
+            DfsEvent::Discover(node) => {
+                indices.insert(node, indices.len());
+                low_links.insert(node, indices[&node]);
+                stack.push(node);
+                on_stack.insert(node);
+            }
+            DfsEvent::TreeEdge { from, to, .. } => {
+                parents.insert(to, from);
+            }
+            DfsEvent::BackEdge { from, to, .. } | DfsEvent::CrossEdge { from, to, .. } => {
+                if on_stack.contains(&to) {
+                    let index = indices[&to];
+                    low_links
+                        .entry(from)
+                        .and_modify(|low| *low = (*low).min(index));
+                }
+            }
+            DfsEvent::Finish(node) => {
+                let low = low_links[&node];
+                if let Some(parent) = parents.get(&node) {
+                    low_links
+                        .entry(*parent)
+                        .and_modify(|parent_low| *parent_low = (*parent_low).min(low));
+                }
+                // the node is the root of its component, which sits on top of the stack. Undirected
+                // tree edges lead back to the parent, so only the roots of the search qualify there
+                if low == indices[&node] && (graph.is_directed() || !parents.contains_key(&node)) {
+                    let position = stack
+                        .iter()
+                        .rposition(|other| *other == node)
+                        .expect("node is on the stack");
+                    let component = stack.split_off(position);
+                    component.iter().for_each(|node| {
+                        on_stack.remove(node);
+                    });
+                    components.push(component);
+                }
+            }
+        }
+        Control::Continue
+    });
+
+    components
+}
+
+/// Finds the strongly connected components with Kosaraju's algorithm. The components are
+/// returned in reverse topological order, i.e. no component has an edge into a later one.
+pub fn kosaraju_scc<G, N, E>(graph: &G) -> Vec<Vec<NodeId<G>>>
+where
+    G: GraphRead<N, E>,
+{
+    // finishing order of a depth first search on the reversed graph
+    let mut visited = HashSet::new();
+    let mut finished = Vec::with_capacity(graph.node_count());
+    for start in graph.nodes() {
+        if !visited.insert(start) {
+            continue;
+        }
+        let mut stack = vec![(start, graph.incoming_nodes(start))];
+        while let Some((node, predecessors)) = stack.last_mut() {
+            match predecessors.find(|predecessor| !visited.contains(predecessor)) {
+                Some(predecessor) => {
+                    visited.insert(predecessor);
+                    stack.push((predecessor, graph.incoming_nodes(predecessor)));
+                }
+                None => {
+                    finished.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    // nodes finished last on the reversed graph can't reach other unassigned components
+    let mut assigned = HashSet::new();
+    let mut components = Vec::new();
+    for start in finished.into_iter().rev() {
+        if !assigned.insert(start) {
+            continue;
+        }
+        let mut component = Vec::new();
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            component.push(node);
+            graph.outcoming_nodes(node).for_each(|successor| {
+                if assigned.insert(successor) {
+                    stack.push(successor);
+                }
+            });
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+/// Collapses every strongly connected component into a single node which holds the weights of
+/// its nodes. Edges within a component are dropped, all others are kept, so the condensed graph
+/// is acyclic but may have parallel edges. Its nodes are added in topological order.
+///
+/// Also returns which node of the condensed graph every node of the original graph ended up in.
+#[allow(clippy::type_complexity)]
+pub fn condensation<G, N, E>(
+    graph: &G,
+) -> (
+    Condensation<N, E>,
+    HashMap<NodeId<G>, NodeId<Condensation<N, E>>>,
+)
+where
+    G: GraphRead<N, E>,
+    N: Clone,
+    E: Clone,
+{
+    let mut condensed = Graph::multigraph();
+    let mut mapping = HashMap::with_capacity(graph.node_count());
+
+    tarjan_scc(graph).into_iter().rev().for_each(|component| {
+        let weights = component
+            .iter()
+            .filter_map(|node| graph.node(*node).cloned())
+            .collect();
+        let id = condensed.add_node(weights);
+        component.into_iter().for_each(|node| {
+            mapping.insert(node, id);
+        });
+    });
+
+    graph.edges().for_each(|edge| {
+        let (Some(ends), Some(weight)) = (graph.edge_ends(edge), graph.edge(edge)) else {
+            return;
+        };
+        let (from, to) = (mapping[&ends.from], mapping[&ends.to]);
+        if from != to {
+            condensed.add_edge(from, to, weight.clone());
+        }
+    });
+
+    (condensed, mapping)
+}
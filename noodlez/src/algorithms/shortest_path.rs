@@ -0,0 +1,715 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Debug;
+use std::ops::Sub;
+
+use crate::storage::{edge::EdgeId, node::NodeId, traits::GraphRead};
+
+use super::{
+    arcs, dense_indices,
+    measure::{Measure, MinScored},
+    opposite, Cycle,
+};
+
+/// Distances and predecessors of all nodes which were reached from a source node
+pub struct ShortestPaths<G, C> {
+    source: NodeId<G>,
+    distances: HashMap<NodeId<G>, C>,
+    predecessors: HashMap<NodeId<G>, (NodeId<G>, EdgeId<G>)>,
+}
+
+impl<G, C: Copy> ShortestPaths<G, C> {
+    fn new(source: NodeId<G>) -> Self {
+        Self {
+            source,
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    pub fn source(&self) -> NodeId<G> {
+        self.source
+    }
+
+    /// Cost of the shortest path from the source to the node, if it was reached
+    #[inline]
+    pub fn distance(&self, node: NodeId<G>) -> Option<C> {
+        self.distances.get(&node).copied()
+    }
+
+    #[inline]
+    pub fn distances(&self) -> impl Iterator<Item = (NodeId<G>, C)> + '_ {
+        self.distances.iter().map(|(node, cost)| (*node, *cost))
+    }
+
+    /// Node before this one on the shortest path from the source
+    #[inline]
+    pub fn predecessor(&self, node: NodeId<G>) -> Option<NodeId<G>> {
+        self.predecessors.get(&node).map(|(node, _)| *node)
+    }
+
+    /// Nodes on the shortest path from the source to the target, both included
+    pub fn path_to(&self, target: NodeId<G>) -> Option<Vec<NodeId<G>>> {
+        self.distances.contains_key(&target).then(|| {
+            let mut path = vec![target];
+            path.extend(self.walk_back(target).map(|(node, _)| node));
+            path.reverse();
+            path
+        })
+    }
+
+    /// Edges on the shortest path from the source to the target
+    pub fn edge_path_to(&self, target: NodeId<G>) -> Option<Vec<EdgeId<G>>> {
+        self.distances.contains_key(&target).then(|| {
+            let mut path = self
+                .walk_back(target)
+                .map(|(_, edge)| edge)
+                .collect::<Vec<_>>();
+            path.reverse();
+            path
+        })
+    }
+
+    fn walk_back(&self, target: NodeId<G>) -> impl Iterator<Item = (NodeId<G>, EdgeId<G>)> + '_ {
+        std::iter::successors(self.predecessors.get(&target), |(node, _)| {
+            self.predecessors.get(node)
+        })
+        .copied()
+    }
+}
+
+impl<G, C: Clone> Clone for ShortestPaths<G, C> {
+    fn clone(&self) -> Self {
+        Self {
+            source: self.source,
+            distances: self.distances.clone(),
+            predecessors: self.predecessors.clone(),
+        }
+    }
+}
+
+impl<G, C: Debug> Debug for ShortestPaths<G, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShortestPaths")
+            .field("source", &self.source)
+            .field("distances", &self.distances)
+            .field("predecessors", &self.predecessors)
+            .finish()
+    }
+}
+
+/// Computes the shortest paths from the source to all reachable nodes, stopping early once the
+/// target was settled. Costs must not be negative.
+pub fn dijkstra<G, N, E, C>(
+    graph: &G,
+    source: NodeId<G>,
+    target: Option<NodeId<G>>,
+    mut cost: impl FnMut(EdgeId<G>, &E) -> C,
+) -> ShortestPaths<G, C>
+where
+    G: GraphRead<N, E>,
+    C: Measure,
+{
+    best_first_search(
+        graph,
+        source,
+        target,
+        |edge, weight| Some(cost(edge, weight)),
+        |_| C::default(),
+    )
+}
+
+/// Computes the shortest path from the source to the target, guided by a heuristic which
+/// estimates the remaining cost to the target from a node. The path is only guaranteed to be the
+/// shortest one if the heuristic never overestimates. Costs must not be negative.
+pub fn astar<G, N, E, C>(
+    graph: &G,
+    source: NodeId<G>,
+    target: NodeId<G>,
+    mut cost: impl FnMut(EdgeId<G>, &E) -> C,
+    heuristic: impl FnMut(NodeId<G>) -> C,
+) -> ShortestPaths<G, C>
+where
+    G: GraphRead<N, E>,
+    C: Measure,
+{
+    best_first_search(
+        graph,
+        source,
+        Some(target),
+        |edge, weight| Some(cost(edge, weight)),
+        heuristic,
+    )
+}
+
+// edges without a cost can't be used
+fn best_first_search<G, N, E, C>(
+    graph: &G,
+    source: NodeId<G>,
+    target: Option<NodeId<G>>,
+    mut cost: impl FnMut(EdgeId<G>, &E) -> Option<C>,
+    mut heuristic: impl FnMut(NodeId<G>) -> C,
+) -> ShortestPaths<G, C>
+where
+    G: GraphRead<N, E>,
+    C: Measure,
+{
+    let mut paths = ShortestPaths::new(source);
+    if !graph.contains_node(source) {
+        return paths;
+    }
+
+    let mut heap = BinaryHeap::new();
+    paths.distances.insert(source, C::default());
+    heap.push(MinScored(heuristic(source), (C::default(), source)));
+
+    while let Some(MinScored(_, (distance, node))) = heap.pop() {
+        // the node was already reached on a shorter path after this entry was pushed
+        if paths
+            .distances
+            .get(&node)
+            .is_some_and(|best| distance > *best)
+        {
+            continue;
+        }
+        if target == Some(node) {
+            break;
+        }
+
+        for edge in graph.outcoming_edges(node) {
+            let (Some(next), Some(weight)) = (opposite(graph, edge, node), graph.edge(edge)) else {
+                continue;
+            };
+            let Some(cost) = cost(edge, weight) else {
+                continue;
+            };
+            let next_distance = distance + cost;
+            if paths
+                .distances
+                .get(&next)
+                .is_some_and(|best| next_distance >= *best)
+            {
+                continue;
+            }
+            paths.distances.insert(next, next_distance);
+            paths.predecessors.insert(next, (node, edge));
+            heap.push(MinScored(
+                next_distance + heuristic(next),
+                (next_distance, next),
+            ));
+        }
+    }
+
+    paths
+}
+
+/// Computes the shortest paths from the source to all reachable nodes, allowing negative costs.
+/// Fails with a reachable cycle of negative total cost, since no shortest paths exist then. In
+/// undirected graphs every negative edge forms such a cycle on its own.
+pub fn bellman_ford<G, N, E, C>(
+    graph: &G,
+    source: NodeId<G>,
+    cost: impl FnMut(EdgeId<G>, &E) -> C,
+) -> Result<ShortestPaths<G, C>, Cycle<G>>
+where
+    G: GraphRead<N, E>,
+    C: Measure,
+{
+    let mut paths = ShortestPaths::new(source);
+    if !graph.contains_node(source) {
+        return Ok(paths);
+    }
+
+    paths.distances.insert(source, C::default());
+    relax(graph, &mut paths, &arcs(graph, cost))?;
+    Ok(paths)
+}
+
+// relaxes the arcs until the distances settle, starting from the distances which are already set
+#[allow(clippy::type_complexity)]
+fn relax<G, N, E, C>(
+    graph: &G,
+    paths: &mut ShortestPaths<G, C>,
+    arcs: &[(NodeId<G>, NodeId<G>, EdgeId<G>, C)],
+) -> Result<(), Cycle<G>>
+where
+    G: GraphRead<N, E>,
+    C: Measure,
+{
+    // a shortest path has at most `node_count - 1` edges, so anything still relaxed in the
+    // round after that was reached through a negative cycle
+    for round in 0..graph.node_count() {
+        let mut relaxed = None;
+        for &(from, to, edge, cost) in arcs {
+            let Some(distance) = paths.distances.get(&from).map(|d| *d + cost) else {
+                continue;
+            };
+            if paths
+                .distances
+                .get(&to)
+                .is_some_and(|best| distance >= *best)
+            {
+                continue;
+            }
+            paths.distances.insert(to, distance);
+            paths.predecessors.insert(to, (from, edge));
+            relaxed = Some(to);
+        }
+
+        match relaxed {
+            None => break,
+            Some(node) if round + 1 == graph.node_count() => {
+                return Err(negative_cycle(paths, node, graph.node_count()));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+// follows the predecessors of a node which was relaxed too often until it ends up on the cycle
+fn negative_cycle<G, C: Copy>(
+    paths: &ShortestPaths<G, C>,
+    node: NodeId<G>,
+    steps: usize,
+) -> Cycle<G> {
+    let back = |node: NodeId<G>| paths.predecessors[&node].0;
+    let start = (0..steps).fold(node, |node, _| back(node));
+
+    let mut nodes = vec![start];
+    let mut node = back(start);
+    while node != start {
+        nodes.push(node);
+        node = back(node);
+    }
+    nodes.reverse();
+    Cycle { nodes }
+}
+
+/// Distances between all pairs of nodes where one reaches the other, optionally with the last
+/// step of every shortest path so the paths can be reconstructed
+pub struct AllShortestPaths<G, C> {
+    distances: HashMap<(NodeId<G>, NodeId<G>), C>,
+    #[allow(clippy::type_complexity)]
+    predecessors: Option<HashMap<(NodeId<G>, NodeId<G>), (NodeId<G>, EdgeId<G>)>>,
+}
+
+impl<G, C: Copy> AllShortestPaths<G, C> {
+    /// Cost of the shortest path between the nodes, if there is one
+    #[inline]
+    pub fn distance(&self, from: NodeId<G>, to: NodeId<G>) -> Option<C> {
+        self.distances.get(&(from, to)).copied()
+    }
+
+    #[inline]
+    pub fn distances(&self) -> &HashMap<(NodeId<G>, NodeId<G>), C> {
+        &self.distances
+    }
+
+    #[inline]
+    pub fn into_distances(self) -> HashMap<(NodeId<G>, NodeId<G>), C> {
+        self.distances
+    }
+
+    /// Nodes on the shortest path between the nodes, both included. Only available if the paths
+    /// were recorded.
+    pub fn path(&self, from: NodeId<G>, to: NodeId<G>) -> Option<Vec<NodeId<G>>> {
+        let mut path = vec![to];
+        path.extend(self.walk_back(from, to)?.map(|(node, _)| node));
+        path.reverse();
+        Some(path)
+    }
+
+    /// Edges on the shortest path between the nodes. Only available if the paths were recorded.
+    pub fn edge_path(&self, from: NodeId<G>, to: NodeId<G>) -> Option<Vec<EdgeId<G>>> {
+        let mut path = self
+            .walk_back(from, to)?
+            .map(|(_, edge)| edge)
+            .collect::<Vec<_>>();
+        path.reverse();
+        Some(path)
+    }
+
+    fn walk_back(
+        &self,
+        from: NodeId<G>,
+        to: NodeId<G>,
+    ) -> Option<impl Iterator<Item = (NodeId<G>, EdgeId<G>)> + '_> {
+        let predecessors = self.predecessors.as_ref()?;
+        self.distances.contains_key(&(from, to)).then(|| {
+            std::iter::successors(predecessors.get(&(from, to)), move |(node, _)| {
+                predecessors.get(&(from, *node))
+            })
+            .copied()
+        })
+    }
+}
+
+impl<G, C: Clone> Clone for AllShortestPaths<G, C> {
+    fn clone(&self) -> Self {
+        Self {
+            distances: self.distances.clone(),
+            predecessors: self.predecessors.clone(),
+        }
+    }
+}
+
+impl<G, C: Debug> Debug for AllShortestPaths<G, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AllShortestPaths")
+            .field("distances", &self.distances)
+            .field("predecessors", &self.predecessors)
+            .finish()
+    }
+}
+
+/// Computes the shortest paths between all pairs of nodes with the Floyd-Warshall algorithm,
+/// which takes cubic time and quadratic memory regardless of the edge count, so it suits dense
+/// graphs. Negative costs are allowed, negative cycles fail like in [`bellman_ford`].
+pub fn floyd_warshall<G, N, E, C>(
+    graph: &G,
+    cost: impl FnMut(EdgeId<G>, &E) -> C,
+    record_paths: bool,
+) -> Result<AllShortestPaths<G, C>, Cycle<G>>
+where
+    G: GraphRead<N, E>,
+    C: Measure,
+{
+    let (nodes, indices) = dense_indices(graph);
+    let count = nodes.len();
+    let arcs = arcs(graph, cost);
+
+    // dense matrices indexed by `from * count + to`
+    let mut distances = vec![None::<C>; count * count];
+    let mut predecessors = vec![None; count * count];
+    (0..count).for_each(|node| distances[node * count + node] = Some(C::default()));
+    for &(from, to, edge, cost) in &arcs {
+        let (from, to) = (indices[&from], indices[&to]);
+        let entry = from * count + to;
+        if distances[entry].is_none_or(|best| cost < best) {
+            distances[entry] = Some(cost);
+            predecessors[entry] = Some((from, edge));
+        }
+    }
+
+    for middle in 0..count {
+        for from in 0..count {
+            let Some(first) = distances[from * count + middle] else {
+                continue;
+            };
+            for to in 0..count {
+                let Some(second) = distances[middle * count + to] else {
+                    continue;
+                };
+                let entry = from * count + to;
+                if distances[entry].is_none_or(|best| first + second < best) {
+                    distances[entry] = Some(first + second);
+                    predecessors[entry] = predecessors[middle * count + to];
+                }
+            }
+        }
+
+        // distances around a negative cycle would keep dropping, so stop at the first one and
+        // let bellman-ford find it
+        for node in 0..count {
+            if distances[node * count + node].is_some_and(|cost| cost < C::default()) {
+                let mut paths = ShortestPaths::new(nodes[node]);
+                paths.distances.insert(nodes[node], C::default());
+                relax(graph, &mut paths, &arcs)?;
+            }
+        }
+    }
+
+    let pair = |entry: usize| (nodes[entry / count], nodes[entry % count]);
+    Ok(AllShortestPaths {
+        distances: distances
+            .into_iter()
+            .enumerate()
+            .filter_map(|(entry, distance)| Some((pair(entry), distance?)))
+            .collect(),
+        predecessors: record_paths.then(|| {
+            predecessors
+                .into_iter()
+                .enumerate()
+                .filter_map(|(entry, predecessor)| {
+                    let (from, edge) = predecessor?;
+                    Some((pair(entry), (nodes[from], edge)))
+                })
+                .collect()
+        }),
+    })
+}
+
+/// Computes the shortest paths between all pairs of nodes with Johnson's algorithm, which suits
+/// sparse graphs. Negative costs are allowed: they are shifted by node potentials from
+/// bellman-ford so that dijkstra can run from every node. Negative cycles fail like in
+/// [`bellman_ford`].
+pub fn johnson<G, N, E, C>(
+    graph: &G,
+    cost: impl FnMut(EdgeId<G>, &E) -> C,
+    record_paths: bool,
+) -> Result<AllShortestPaths<G, C>, Cycle<G>>
+where
+    G: GraphRead<N, E>,
+    C: Measure + Sub<Output = C>,
+{
+    let mut all = AllShortestPaths {
+        distances: HashMap::new(),
+        predecessors: record_paths.then(HashMap::new),
+    };
+    let Some(first) = graph.nodes().next() else {
+        return Ok(all);
+    };
+
+    // starting every node at zero acts like an extra source with an edge to each node
+    let arcs = arcs(graph, cost);
+    let mut potentials = ShortestPaths::new(first);
+    graph.nodes().for_each(|node| {
+        potentials.distances.insert(node, C::default());
+    });
+    relax(graph, &mut potentials, &arcs)?;
+    let potential = |node: NodeId<G>| potentials.distances[&node];
+
+    // without negative cycles undirected edges aren't negative, so all potentials stay zero and
+    // the direction an undirected edge is used in doesn't matter
+    let costs = arcs
+        .iter()
+        .map(|&(from, to, edge, cost)| (edge, cost + potential(from) - potential(to)))
+        .collect::<HashMap<_, _>>();
+
+    for source in graph.nodes() {
+        let paths = dijkstra(graph, source, None, |edge, _| costs[&edge]);
+        all.distances
+            .extend(paths.distances.into_iter().map(|(target, distance)| {
+                let distance = distance + potential(target) - potential(source);
+                ((source, target), distance)
+            }));
+        if let Some(predecessors) = all.predecessors.as_mut() {
+            predecessors.extend(
+                paths
+                    .predecessors
+                    .into_iter()
+                    .map(|(target, predecessor)| ((source, target), predecessor)),
+            );
+        }
+    }
+    Ok(all)
+}
+
+/// A loopless path through the graph together with its total cost
+pub struct Path<G, C> {
+    cost: C,
+    nodes: Vec<NodeId<G>>,
+    edges: Vec<EdgeId<G>>,
+}
+
+impl<G, C: Copy> Path<G, C> {
+    #[inline]
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// Nodes along the path, both ends included
+    #[inline]
+    pub fn nodes(&self) -> &[NodeId<G>] {
+        &self.nodes
+    }
+
+    #[inline]
+    pub fn edges(&self) -> &[EdgeId<G>] {
+        &self.edges
+    }
+}
+
+impl<G, C: Clone> Clone for Path<G, C> {
+    fn clone(&self) -> Self {
+        Self {
+            cost: self.cost.clone(),
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+        }
+    }
+}
+
+impl<G, C: Debug> Debug for Path<G, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Path")
+            .field("cost", &self.cost)
+            .field("nodes", &self.nodes)
+            .field("edges", &self.edges)
+            .finish()
+    }
+}
+
+/// Finds up to `k` loopless paths from the source to the target with Yen's algorithm, yielding
+/// them lazily in increasing cost. Paths over different parallel edges count as different paths.
+/// Costs must not be negative.
+pub fn k_shortest_paths<'a, G, N, E, C>(
+    graph: &'a G,
+    source: NodeId<G>,
+    target: NodeId<G>,
+    k: usize,
+    cost: impl FnMut(EdgeId<G>, &E) -> C,
+) -> impl Iterator<Item = Path<G, C>> + 'a
+where
+    G: GraphRead<N, E>,
+    C: Measure + 'a,
+    N: 'a,
+    E: 'a,
+{
+    k_shortest_paths_excluding(graph, source, target, k, cost, [], [])
+}
+
+/// Like [`k_shortest_paths`], but the paths avoid the given nodes and edges
+pub fn k_shortest_paths_excluding<'a, G, N, E, C>(
+    graph: &'a G,
+    source: NodeId<G>,
+    target: NodeId<G>,
+    k: usize,
+    mut cost: impl FnMut(EdgeId<G>, &E) -> C,
+    excluded_nodes: impl IntoIterator<Item = NodeId<G>>,
+    excluded_edges: impl IntoIterator<Item = EdgeId<G>>,
+) -> impl Iterator<Item = Path<G, C>> + 'a
+where
+    G: GraphRead<N, E>,
+    C: Measure + 'a,
+    N: 'a,
+    E: 'a,
+{
+    let costs = graph
+        .edges()
+        .filter_map(|edge| Some((edge, cost(edge, graph.edge(edge)?))))
+        .collect::<HashMap<_, _>>();
+    let excluded_nodes = excluded_nodes.into_iter().collect::<HashSet<_>>();
+    let excluded_edges = excluded_edges.into_iter().collect::<HashSet<_>>();
+
+    // shortest path from the spur node to the target which avoids the removed nodes and edges
+    let spur_path = move |spur: NodeId<G>,
+                          costs: &HashMap<EdgeId<G>, C>,
+                          removed_nodes: &HashSet<NodeId<G>>,
+                          removed_edges: &HashSet<EdgeId<G>>| {
+        if excluded_nodes.contains(&spur) {
+            return None;
+        }
+        let usable = |edge: EdgeId<G>| {
+            !excluded_edges.contains(&edge)
+                && !removed_edges.contains(&edge)
+                && graph.edge_ends(edge).is_some_and(|ends| {
+                    [ends.from, ends.to]
+                        .iter()
+                        .all(|node| !excluded_nodes.contains(node) && !removed_nodes.contains(node))
+                })
+        };
+        let paths = best_first_search(
+            graph,
+            spur,
+            Some(target),
+            |edge, _| usable(edge).then(|| costs[&edge]),
+            |_| C::default(),
+        );
+        Some((
+            paths.distance(target)?,
+            paths.path_to(target)?,
+            paths.edge_path_to(target)?,
+        ))
+    };
+
+    let mut found = Vec::<Path<G, C>>::new();
+    let mut candidates = Vec::new();
+    let mut heap = BinaryHeap::new();
+    let mut seen = HashSet::new();
+    std::iter::from_fn(move || {
+        if found.len() >= k {
+            return None;
+        }
+        let Some(previous) = found.last() else {
+            let (cost, nodes, edges) = spur_path(source, &costs, &HashSet::new(), &HashSet::new())?;
+            seen.insert(edges.clone());
+            found.push(Path { cost, nodes, edges });
+            return found.last().cloned();
+        };
+
+        // branch off the previous path at every node, keeping the part up to there
+        let mut root_cost = C::default();
+        for (spur_index, &spur) in previous.nodes[..previous.edges.len()].iter().enumerate() {
+            let root_edges = &previous.edges[..spur_index];
+            let removed_nodes = previous.nodes[..spur_index].iter().copied().collect();
+            // the next edges of known paths with the same root would lead to them again
+            let removed_edges = found
+                .iter()
+                .filter(|path| path.edges.starts_with(root_edges))
+                .filter_map(|path| path.edges.get(spur_index).copied())
+                .collect();
+
+            if let Some((spur_cost, spur_nodes, spur_edges)) =
+                spur_path(spur, &costs, &removed_nodes, &removed_edges)
+            {
+                let mut edges = root_edges.to_vec();
+                edges.extend(spur_edges);
+                if seen.insert(edges.clone()) {
+                    let mut nodes = previous.nodes[..spur_index].to_vec();
+                    nodes.extend(spur_nodes);
+                    let cost = root_cost + spur_cost;
+                    heap.push(MinScored(cost, candidates.len()));
+                    candidates.push(Some(Path { cost, nodes, edges }));
+                }
+            }
+            root_cost = root_cost + costs[&previous.edges[spur_index]];
+        }
+
+        let MinScored(_, next) = heap.pop()?;
+        found.push(candidates[next].take()?);
+        found.last().cloned()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn k_shortest_paths_over_parallel_edges() {
+        let mut graph = Graph::<&str, u32>::multigraph();
+        let [s, a, t, u] = ["s", "a", "t", "u"].map(|name| graph.add_node(name));
+        let cheap = graph.add_edge(s, a, 1).unwrap();
+        let expensive = graph.add_edge(s, a, 2).unwrap();
+        let last = graph.add_edge(a, t, 1).unwrap();
+        let direct = graph.add_edge(s, t, 4).unwrap();
+        graph.add_edge(u, s, 1).unwrap();
+
+        let paths = k_shortest_paths(&graph, s, t, 5, |_, cost| *cost).collect::<Vec<_>>();
+        assert_eq!(paths.iter().map(Path::cost).collect::<Vec<_>>(), [2, 3, 4]);
+        assert_eq!(paths[0].nodes(), [s, a, t]);
+        assert_eq!(paths[0].edges(), [cheap, last]);
+        assert_eq!(paths[1].nodes(), [s, a, t]);
+        assert_eq!(paths[1].edges(), [expensive, last]);
+        assert_eq!(paths[2].nodes(), [s, t]);
+        assert_eq!(paths[2].edges(), [direct]);
+
+        assert_eq!(
+            k_shortest_paths(&graph, s, t, 2, |_, cost| *cost).count(),
+            2
+        );
+        // u only has an outgoing edge
+        assert_eq!(
+            k_shortest_paths(&graph, s, u, 5, |_, cost| *cost).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn k_shortest_paths_with_exclusions() {
+        let mut graph = Graph::<&str, u32>::multigraph();
+        let [s, a, t] = ["s", "a", "t"].map(|name| graph.add_node(name));
+        let cheap = graph.add_edge(s, a, 1).unwrap();
+        graph.add_edge(s, a, 2).unwrap();
+        graph.add_edge(a, t, 1).unwrap();
+        graph.add_edge(s, t, 4).unwrap();
+
+        let paths = k_shortest_paths_excluding(&graph, s, t, 5, |_, cost| *cost, [], [cheap]);
+        assert_eq!(paths.map(|path| path.cost()).collect::<Vec<_>>(), [3, 4]);
+        let paths = k_shortest_paths_excluding(&graph, s, t, 5, |_, cost| *cost, [a], []);
+        assert_eq!(paths.map(|path| path.cost()).collect::<Vec<_>>(), [4]);
+    }
+}
@@ -0,0 +1,110 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::storage::{
+    edge::EdgeId,
+    graph::slotmap_graph::Graph,
+    node::NodeId,
+    traits::{GraphRead, GraphWrite},
+};
+
+use super::{components::UnionFind, measure::MinScored};
+
+/// Picks the edges of a minimum spanning forest by adding the cheapest edges first, as long as
+/// they connect two separate trees. Edge directions are ignored.
+pub fn kruskal<G, N, E, C>(graph: &G, mut weight: impl FnMut(EdgeId<G>, &E) -> C) -> Vec<EdgeId<G>>
+where
+    G: GraphRead<N, E>,
+    C: PartialOrd,
+{
+    let mut candidates = graph
+        .edges()
+        .filter_map(|edge| {
+            let ends = graph.edge_ends(edge).filter(|ends| ends.from != ends.to)?;
+            Some((weight(edge, graph.edge(edge)?), edge, ends.from, ends.to))
+        })
+        .collect::<Vec<_>>();
+    // stable, so ties are broken by graph order
+    candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    let mut union_find = UnionFind::new();
+    candidates
+        .into_iter()
+        .filter(|(_, _, from, to)| union_find.union(*from, *to))
+        .map(|(_, edge, _, _)| edge)
+        .collect()
+}
+
+/// Picks the edges of a minimum spanning forest by growing one tree after the other from its
+/// cheapest outgoing edge. Edge directions are ignored.
+pub fn prim<G, N, E, C>(graph: &G, mut weight: impl FnMut(EdgeId<G>, &E) -> C) -> Vec<EdgeId<G>>
+where
+    G: GraphRead<N, E>,
+    C: PartialOrd,
+{
+    let mut visited = HashSet::with_capacity(graph.node_count());
+    let mut forest = Vec::new();
+    let mut heap = BinaryHeap::new();
+
+    let mut visit = |node: NodeId<G>, visited: &mut HashSet<_>, heap: &mut BinaryHeap<_>| {
+        visited.insert(node);
+        graph.neighbor_edges(node).for_each(|edge| {
+            if let Some(weight) = graph.edge(edge).map(|value| weight(edge, value)) {
+                heap.push(MinScored(weight, edge));
+            }
+        });
+    };
+
+    for start in graph.nodes() {
+        if visited.contains(&start) {
+            continue;
+        }
+        visit(start, &mut visited, &mut heap);
+
+        while let Some(MinScored(_, edge)) = heap.pop() {
+            let Some(ends) = graph.edge_ends(edge) else {
+                continue;
+            };
+            let next = match (visited.contains(&ends.from), visited.contains(&ends.to)) {
+                (true, false) => ends.to,
+                (false, true) => ends.from,
+                _ => continue,
+            };
+            forest.push(edge);
+            visit(next, &mut visited, &mut heap);
+        }
+    }
+
+    forest
+}
+
+/// Copies all nodes of the graph and only the given edges into a new graph, e.g. to materialize a
+/// spanning forest. Node and edge order are kept.
+pub fn spanning_forest<G, N, E>(
+    graph: &G,
+    edges: impl IntoIterator<Item = EdgeId<G>>,
+) -> Graph<N, E, G::Direction>
+where
+    G: GraphRead<N, E>,
+    N: Clone,
+    E: Clone,
+{
+    let mut forest = Graph::multigraph();
+    let mapping = graph
+        .nodes()
+        .filter_map(|node| Some((node, forest.add_node(graph.node(node)?.clone()))))
+        .collect::<HashMap<_, _>>();
+
+    let mut edges = edges.into_iter().collect::<HashSet<_>>();
+    graph
+        .edges()
+        .filter(|edge| edges.remove(edge))
+        .for_each(|edge| {
+            let (Some(ends), Some(weight)) = (graph.edge_ends(edge), graph.edge(edge)) else {
+                return;
+            };
+            forest.add_edge(mapping[&ends.from], mapping[&ends.to], weight.clone());
+        });
+
+    forest
+}
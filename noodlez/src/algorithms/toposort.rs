@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::storage::{node::NodeId, traits::GraphRead};
+
+use super::{
+    traversal::{depth_first_search, Control, DfsEvent},
+    Cycle,
+};
+
+/// Sorts the nodes of a directed graph so that every edge points from an earlier to a later node
+pub fn toposort<G, N, E>(graph: &G) -> Result<Vec<NodeId<G>>, Cycle<G>>
+where
+    G: GraphRead<N, E>,
+{
+    toposort_layers(graph).map(|layers| layers.into_iter().flatten().collect())
+}
+
+/// Groups the nodes of a directed graph into layers, where all edges into a node come from nodes
+/// of earlier layers. The nodes of one layer are independent of each other.
+pub fn toposort_layers<G, N, E>(graph: &G) -> Result<Vec<Vec<NodeId<G>>>, Cycle<G>>
+where
+    G: GraphRead<N, E>,
+{
+    let mut in_degrees = graph
+        .nodes()
+        .map(|node| (node, graph.incoming_edges(node).count()))
+        .collect::<HashMap<_, _>>();
+
+    let mut layers = Vec::new();
+    let mut layer = in_degrees
+        .iter()
+        .filter(|(_, degree)| **degree == 0)
+        .map(|(node, _)| *node)
+        .collect::<Vec<_>>();
+    // keep the order of the graph within the layers
+    let order = graph
+        .nodes()
+        .enumerate()
+        .map(|(i, node)| (node, i))
+        .collect::<HashMap<_, _>>();
+
+    while !layer.is_empty() {
+        layer.sort_by_key(|node| order[node]);
+        layer.iter().for_each(|node| {
+            in_degrees.remove(node);
+        });
+        let next = layer
+            .iter()
+            .flat_map(|node| graph.outcoming_nodes(*node))
+            .filter(|next| {
+                in_degrees.get_mut(next).is_some_and(|degree| {
+                    *degree -= 1;
+                    *degree == 0
+                })
+            })
+            .collect();
+        layers.push(std::mem::replace(&mut layer, next));
+    }
+
+    match in_degrees.keys().next() {
+        Some(start) => Err(find_cycle(graph, *start, &in_degrees)),
+        None => Ok(layers),
+    }
+}
+
+/// Checks whether a directed graph contains at least one cycle
+pub fn is_cyclic_directed<G, N, E>(graph: &G) -> bool
+where
+    G: GraphRead<N, E>,
+{
+    depth_first_search(graph, graph.nodes(), |event| match event {
+        DfsEvent::BackEdge { .. } => Control::Break(()),
+        _ => Control::Continue,
+    })
+    .is_some()
+}
+
+// every node which couldn't be sorted still has an incoming edge from another unsorted node, so
+// walking backwards along those edges has to run into a cycle eventually
+fn find_cycle<G, N, E>(
+    graph: &G,
+    start: NodeId<G>,
+    unsorted: &HashMap<NodeId<G>, usize>,
+) -> Cycle<G>
+where
+    G: GraphRead<N, E>,
+{
+    let mut path = vec![start];
+    let mut visited = HashSet::from([start]);
+    let mut current = start;
+    loop {
+        let previous = graph
+            .incoming_nodes(current)
+            .find(|node| unsorted.contains_key(node))
+            .expect("unsorted nodes always have unsorted predecessors");
+        if !visited.insert(previous) {
+            let begin = path.iter().position(|node| *node == previous).unwrap_or(0);
+            // the walk went backwards, so the cycle is the reversed tail of the path
+            let mut nodes = path.split_off(begin);
+            nodes.reverse();
+            return Cycle { nodes };
+        }
+        path.push(previous);
+        current = previous;
+    }
+}
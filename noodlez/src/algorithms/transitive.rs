@@ -0,0 +1,212 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+use crate::storage::{edge::EdgeId, node::NodeId, traits::GraphRead};
+
+use super::{dense_indices, positions, scc::tarjan_scc, toposort::toposort, Cycle};
+
+// square matrix of bits, one row per node
+#[derive(Clone)]
+struct BitMatrix {
+    words: usize,
+    bits: Vec<u64>,
+}
+
+impl BitMatrix {
+    fn new(size: usize) -> Self {
+        let words = size.div_ceil(64);
+        Self {
+            words,
+            bits: vec![0; words * size],
+        }
+    }
+
+    #[inline]
+    fn get(&self, row: usize, column: usize) -> bool {
+        self.bits[row * self.words + column / 64] & (1 << (column % 64)) != 0
+    }
+
+    #[inline]
+    fn set(&mut self, row: usize, column: usize) {
+        self.bits[row * self.words + column / 64] |= 1 << (column % 64);
+    }
+
+    // ors the source row into the target row
+    #[inline]
+    fn merge(&mut self, target: usize, source: usize) {
+        if target != source {
+            let (target, source) = (target * self.words, source * self.words);
+            for word in 0..self.words {
+                self.bits[target + word] |= self.bits[source + word];
+            }
+        }
+    }
+
+    fn row_mut(&mut self, row: usize) -> &mut [u64] {
+        &mut self.bits[row * self.words..(row + 1) * self.words]
+    }
+
+    fn or_into(&self, row: usize, target: &mut [u64]) {
+        let row = &self.bits[row * self.words..(row + 1) * self.words];
+        target
+            .iter_mut()
+            .zip(row)
+            .for_each(|(target, word)| *target |= word);
+    }
+
+    fn row(&self, row: usize) -> impl Iterator<Item = usize> + '_ {
+        self.bits[row * self.words..(row + 1) * self.words]
+            .iter()
+            .enumerate()
+            .flat_map(|(index, word)| {
+                (0..64)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| index * 64 + bit)
+            })
+    }
+}
+
+/// Which nodes can reach which other nodes along paths of at least one edge, so nodes only reach
+/// themselves on cycles. Takes quadratic memory in the number of nodes.
+pub struct TransitiveClosure<G> {
+    nodes: Vec<NodeId<G>>,
+    indices: HashMap<NodeId<G>, usize>,
+    matrix: BitMatrix,
+}
+
+impl<G> TransitiveClosure<G> {
+    /// Whether there is a path from `from` to `to`
+    #[inline]
+    pub fn reaches(&self, from: NodeId<G>, to: NodeId<G>) -> bool {
+        match (self.indices.get(&from), self.indices.get(&to)) {
+            (Some(from), Some(to)) => self.matrix.get(*from, *to),
+            _ => false,
+        }
+    }
+
+    /// All nodes which have a path from the given node
+    pub fn reachable(&self, from: NodeId<G>) -> impl Iterator<Item = NodeId<G>> + '_ {
+        self.indices
+            .get(&from)
+            .into_iter()
+            .flat_map(|from| self.matrix.row(*from))
+            .map(|to| self.nodes[to])
+    }
+}
+
+impl<G> Clone for TransitiveClosure<G> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            indices: self.indices.clone(),
+            matrix: self.matrix.clone(),
+        }
+    }
+}
+
+impl<G> Debug for TransitiveClosure<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.nodes
+                    .iter()
+                    .map(|node| (node, self.reachable(*node).collect::<Vec<_>>())),
+            )
+            .finish()
+    }
+}
+
+/// Computes which nodes reach which other nodes. Nodes of a strongly connected component share
+/// their reachability, so it's computed once per component in reverse topological order.
+pub fn transitive_closure<G, N, E>(graph: &G) -> TransitiveClosure<G>
+where
+    G: GraphRead<N, E>,
+{
+    let (nodes, indices) = dense_indices(graph);
+
+    let components = tarjan_scc(graph);
+    let mut component_of = vec![0; nodes.len()];
+    components
+        .iter()
+        .enumerate()
+        .for_each(|(component, members)| {
+            members
+                .iter()
+                .for_each(|node| component_of[indices[node]] = component);
+        });
+
+    // components come sinks first, so all successors are done before their predecessors
+    let mut matrix = BitMatrix::new(nodes.len());
+    for (component, members) in components.iter().enumerate() {
+        let row = indices[&members[0]];
+        for node in members {
+            for successor in graph.outcoming_nodes(*node) {
+                let successor = indices[&successor];
+                matrix.set(row, successor);
+                if component_of[successor] != component {
+                    matrix.merge(row, indices[&components[component_of[successor]][0]]);
+                }
+            }
+        }
+        // members of a cycle reach each other, the edges of single nodes are already set
+        if members.len() > 1 {
+            members
+                .iter()
+                .for_each(|node| matrix.set(row, indices[node]));
+        }
+        members[1..]
+            .iter()
+            .for_each(|node| matrix.merge(indices[node], row));
+    }
+
+    TransitiveClosure {
+        nodes,
+        indices,
+        matrix,
+    }
+}
+
+/// Finds the edges of a directed acyclic graph which can be removed without changing which
+/// nodes reach each other, i.e. edges parallel to another edge or to a longer path. Fails with a
+/// cycle if the graph isn't acyclic.
+pub fn transitive_reduction<G, N, E>(graph: &G) -> Result<Vec<EdgeId<G>>, Cycle<G>>
+where
+    G: GraphRead<N, E>,
+{
+    let order = toposort(graph)?;
+    let positions = positions(&order);
+
+    // descendants of all later nodes are known before they are needed
+    let mut descendants = BitMatrix::new(order.len());
+    let mut redundant = HashSet::new();
+    for (position, node) in order.iter().enumerate().rev() {
+        // nodes which can be reached through a successor
+        let mut indirect = vec![0; descendants.words];
+        graph.outcoming_nodes(*node).for_each(|successor| {
+            descendants.or_into(positions[&successor], &mut indirect);
+        });
+
+        let mut direct = vec![0; descendants.words];
+        graph.outcoming_edges(*node).for_each(|edge| {
+            let Some(successor) = graph.edge_ends(edge).map(|ends| positions[&ends.to]) else {
+                return;
+            };
+            let bit = 1 << (successor % 64);
+            if (indirect[successor / 64] | direct[successor / 64]) & bit != 0 {
+                redundant.insert(edge);
+            }
+            direct[successor / 64] |= bit;
+        });
+
+        descendants
+            .row_mut(position)
+            .iter_mut()
+            .zip(indirect.into_iter().zip(direct))
+            .for_each(|(word, (indirect, direct))| *word = indirect | direct);
+    }
+
+    Ok(graph
+        .edges()
+        .filter(|edge| redundant.contains(edge))
+        .collect())
+}
@@ -0,0 +1,280 @@
+use std::collections::{HashSet, VecDeque};
+use std::marker::PhantomData;
+
+use crate::storage::{edge::EdgeId, node::NodeId, traits::GraphRead};
+
+use super::opposite;
+
+/// Breadth first walk over all nodes reachable from the start node
+pub struct Bfs<'a, G, N, E> {
+    graph: &'a G,
+    queue: VecDeque<NodeId<G>>,
+    discovered: HashSet<NodeId<G>>,
+    _weights: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, G: GraphRead<N, E>, N, E> Bfs<'a, G, N, E> {
+    pub fn new(graph: &'a G, start: NodeId<G>) -> Self {
+        let mut bfs = Self {
+            graph,
+            queue: VecDeque::new(),
+            discovered: HashSet::new(),
+            _weights: PhantomData,
+        };
+        bfs.move_to(start);
+        bfs
+    }
+
+    /// Continues the walk at another node, nodes that were already visited are skipped
+    pub fn move_to(&mut self, start: NodeId<G>) {
+        if self.graph.contains_node(start) && self.discovered.insert(start) {
+            self.queue.push_back(start);
+        }
+    }
+}
+
+impl<'a, G: GraphRead<N, E>, N, E> Iterator for Bfs<'a, G, N, E> {
+    type Item = NodeId<G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.queue.pop_front()?;
+        self.graph.outcoming_nodes(node).for_each(|next| {
+            if self.discovered.insert(next) {
+                self.queue.push_back(next);
+            }
+        });
+        Some(node)
+    }
+}
+
+/// Depth first walk over all nodes reachable from the start node, yielding nodes in preorder
+pub struct Dfs<'a, G, N, E> {
+    graph: &'a G,
+    stack: Vec<NodeId<G>>,
+    discovered: HashSet<NodeId<G>>,
+    _weights: PhantomData<fn() -> (N, E)>,
+}
+
+impl<'a, G: GraphRead<N, E>, N, E> Dfs<'a, G, N, E> {
+    pub fn new(graph: &'a G, start: NodeId<G>) -> Self {
+        let mut dfs = Self {
+            graph,
+            stack: Vec::new(),
+            discovered: HashSet::new(),
+            _weights: PhantomData,
+        };
+        dfs.move_to(start);
+        dfs
+    }
+
+    /// Continues the walk at another node, nodes that were already visited are skipped
+    pub fn move_to(&mut self, start: NodeId<G>) {
+        if self.graph.contains_node(start) {
+            self.stack.push(start);
+        }
+    }
+}
+
+impl<'a, G: GraphRead<N, E>, N, E> Iterator for Dfs<'a, G, N, E> {
+    type Item = NodeId<G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            if !self.discovered.insert(node) {
+                continue;
+            }
+            let first = self.stack.len();
+            self.stack.extend(
+                self.graph
+                    .outcoming_nodes(node)
+                    .filter(|next| !self.discovered.contains(next)),
+            );
+            // visit successors in the order the graph reports them
+            self.stack[first..].reverse();
+            return Some(node);
+        }
+        None
+    }
+}
+
+/// Events emitted by [`depth_first_search`]
+pub enum DfsEvent<G> {
+    /// The node is visited for the first time
+    Discover(NodeId<G>),
+    /// The edge leads to an undiscovered node which is discovered through it
+    TreeEdge {
+        from: NodeId<G>,
+        to: NodeId<G>,
+        edge: EdgeId<G>,
+    },
+    /// The edge leads to an ancestor of `from` in the search tree
+    BackEdge {
+        from: NodeId<G>,
+        to: NodeId<G>,
+        edge: EdgeId<G>,
+    },
+    /// The edge leads to an already finished node, either a descendant or a node of another
+    /// branch. Only happens in directed graphs
+    CrossEdge {
+        from: NodeId<G>,
+        to: NodeId<G>,
+        edge: EdgeId<G>,
+    },
+    /// All nodes reachable from the node were visited
+    Finish(NodeId<G>),
+}
+
+impl<G> Clone for DfsEvent<G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G> Copy for DfsEvent<G> {}
+
+impl<G> std::fmt::Debug for DfsEvent<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Discover(node) => f.debug_tuple("Discover").field(node).finish(),
+            Self::TreeEdge { from, to, edge } => {
+                write!(f, "TreeEdge({from:?} -> {to:?}, {edge:?})")
+            }
+            Self::BackEdge { from, to, edge } => {
+                write!(f, "BackEdge({from:?} -> {to:?}, {edge:?})")
+            }
+            Self::CrossEdge { from, to, edge } => {
+                write!(f, "CrossEdge({from:?} -> {to:?}, {edge:?})")
+            }
+            Self::Finish(node) => f.debug_tuple("Finish").field(node).finish(),
+        }
+    }
+}
+
+impl<G> PartialEq for DfsEvent<G> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Discover(a), Self::Discover(b)) | (Self::Finish(a), Self::Finish(b)) => a == b,
+            (
+                Self::TreeEdge { from, to, edge },
+                Self::TreeEdge {
+                    from: f,
+                    to: t,
+                    edge: e,
+                },
+            )
+            | (
+                Self::BackEdge { from, to, edge },
+                Self::BackEdge {
+                    from: f,
+                    to: t,
+                    edge: e,
+                },
+            )
+            | (
+                Self::CrossEdge { from, to, edge },
+                Self::CrossEdge {
+                    from: f,
+                    to: t,
+                    edge: e,
+                },
+            ) => from == f && to == t && edge == e,
+            _ => false,
+        }
+    }
+}
+
+impl<G> Eq for DfsEvent<G> {}
+
+/// Return value of the visitor of [`depth_first_search`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control<B> {
+    Continue,
+    /// Don't explore further from here. On `Discover` the successors of the node are skipped, on
+    /// `TreeEdge` the edge isn't followed. Has no effect on other events
+    Prune,
+    /// Stop the search and return the value
+    Break(B),
+}
+
+/// Depth first search from all start nodes which reports every step to the visitor
+///
+/// Returns the value of the first `Control::Break`. In undirected graphs the edge that was used to
+/// discover a node is not reported again as a back edge.
+pub fn depth_first_search<G, N, E, B>(
+    graph: &G,
+    starts: impl IntoIterator<Item = NodeId<G>>,
+    mut visitor: impl FnMut(DfsEvent<G>) -> Control<B>,
+) -> Option<B>
+where
+    G: GraphRead<N, E>,
+{
+    let mut discovered = HashSet::new();
+    let mut finished = HashSet::new();
+
+    for start in starts {
+        if !graph.contains_node(start) || discovered.contains(&start) {
+            continue;
+        }
+
+        discovered.insert(start);
+        let mut stack = Vec::new();
+        match visitor(DfsEvent::Discover(start)) {
+            Control::Break(b) => return Some(b),
+            Control::Prune => {
+                finished.insert(start);
+                if let Control::Break(b) = visitor(DfsEvent::Finish(start)) {
+                    return Some(b);
+                }
+                continue;
+            }
+            Control::Continue => stack.push((start, None, graph.outcoming_edges(start))),
+        }
+
+        while let Some((from, parent_edge, edges)) = stack.last_mut() {
+            let from = *from;
+            let Some(edge) = edges.next() else {
+                stack.pop();
+                finished.insert(from);
+                if let Control::Break(b) = visitor(DfsEvent::Finish(from)) {
+                    return Some(b);
+                }
+                continue;
+            };
+            if !graph.is_directed() && Some(edge) == *parent_edge {
+                continue;
+            }
+            let Some(to) = opposite(graph, edge, from) else {
+                continue;
+            };
+
+            if !discovered.contains(&to) {
+                match visitor(DfsEvent::TreeEdge { from, to, edge }) {
+                    Control::Break(b) => return Some(b),
+                    Control::Prune => continue,
+                    Control::Continue => {}
+                }
+                discovered.insert(to);
+                match visitor(DfsEvent::Discover(to)) {
+                    Control::Break(b) => return Some(b),
+                    Control::Prune => {
+                        finished.insert(to);
+                        if let Control::Break(b) = visitor(DfsEvent::Finish(to)) {
+                            return Some(b);
+                        }
+                    }
+                    Control::Continue => stack.push((to, Some(edge), graph.outcoming_edges(to))),
+                }
+            } else if !finished.contains(&to) {
+                if let Control::Break(b) = visitor(DfsEvent::BackEdge { from, to, edge }) {
+                    return Some(b);
+                }
+            } else if graph.is_directed() {
+                if let Control::Break(b) = visitor(DfsEvent::CrossEdge { from, to, edge }) {
+                    return Some(b);
+                }
+            }
+        }
+    }
+
+    None
+}
@@ -6,9 +6,12 @@
 //     clippy::cargo
 // )]
 
+pub mod algorithms;
 pub mod storage;
 
 pub mod prelude {
+    pub use crate::storage::direction::{Directed, Undirected};
+    pub use crate::storage::error::GraphError;
     pub use crate::storage::graph::slotmap_graph::Graph;
-    pub use crate::storage::traits::GraphStorage;
+    pub use crate::storage::traits::{GraphRead, GraphStorage, GraphWrite};
 }
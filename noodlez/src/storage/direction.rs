@@ -0,0 +1,20 @@
+/// Type level marker for the interpretation of edges in a graph
+pub trait EdgeDirection {
+    const DIRECTED: bool;
+}
+
+/// Edges point from one node to another
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Directed;
+
+/// Edges connect both of their nodes without any specific direction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Undirected;
+
+impl EdgeDirection for Directed {
+    const DIRECTED: bool = true;
+}
+
+impl EdgeDirection for Undirected {
+    const DIRECTED: bool = false;
+}
@@ -2,6 +2,8 @@ use core::hash::Hash;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use slotmap::{Key, KeyData};
+
 use super::node::NodeId;
 
 slotmap::new_key_type! {
@@ -27,6 +29,26 @@ impl<G> EdgeId<G> {
     pub(crate) fn get(self) -> InternalEdgeID {
         self.id
     }
+
+    // index of the slot the id points to, independent of its version
+    #[inline]
+    pub(crate) fn slot(self) -> u32 {
+        self.id.data().as_ffi() as u32
+    }
+
+    // version of the slot the id was handed out for
+    #[inline]
+    pub(crate) fn version(self) -> u32 {
+        (self.id.data().as_ffi() >> 32) as u32
+    }
+
+    // id for storages which address their elements with dense indices instead of a slotmap
+    #[inline]
+    pub(crate) fn from_slot(slot: u32) -> Self {
+        Self::new(InternalEdgeID::from(KeyData::from_ffi(
+            1 << 32 | u64::from(slot),
+        )))
+    }
 }
 
 impl<G> Clone for EdgeId<G> {
@@ -51,11 +73,34 @@ impl<G> PartialEq for EdgeId<G> {
 
 impl<G> Eq for EdgeId<G> {}
 
+impl<G> Hash for EdgeId<G> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
 pub struct EdgeEnds<G> {
     pub(crate) from: NodeId<G>,
     pub(crate) to: NodeId<G>,
 }
 
+impl<G> EdgeEnds<G> {
+    #[inline]
+    pub fn new(from: NodeId<G>, to: NodeId<G>) -> Self {
+        Self { from, to }
+    }
+
+    #[inline]
+    pub fn from(&self) -> NodeId<G> {
+        self.from
+    }
+
+    #[inline]
+    pub fn to(&self) -> NodeId<G> {
+        self.to
+    }
+}
+
 impl<G> Clone for EdgeEnds<G> {
     fn clone(&self) -> Self {
         *self
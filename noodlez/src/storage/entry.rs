@@ -0,0 +1,71 @@
+use super::{edge::EdgeId, node::NodeId};
+
+/// View into the weight behind a single id of a graph
+///
+/// Since ids are handed out by the graph itself, a vacant entry can't be filled and only reports
+/// that the id isn't part of the graph (anymore).
+#[derive(Debug)]
+pub enum Entry<'a, K, V> {
+    Occupied(K, &'a mut V),
+    Vacant(K),
+}
+
+pub type NodeEntry<'a, G, N> = Entry<'a, NodeId<G>, N>;
+pub type EdgeEntry<'a, G, E> = Entry<'a, EdgeId<G>, E>;
+
+impl<'a, K: Copy, V> Entry<'a, K, V> {
+    #[inline]
+    pub fn key(&self) -> K {
+        match self {
+            Self::Occupied(key, _) | Self::Vacant(key) => *key,
+        }
+    }
+
+    #[inline]
+    pub fn is_occupied(&self) -> bool {
+        matches!(self, Self::Occupied(..))
+    }
+
+    #[inline]
+    pub fn is_vacant(&self) -> bool {
+        matches!(self, Self::Vacant(_))
+    }
+
+    #[inline]
+    pub fn and_modify(mut self, f: impl FnOnce(&mut V)) -> Self {
+        if let Self::Occupied(_, value) = &mut self {
+            f(value);
+        }
+        self
+    }
+
+    /// Replaces the weight of an occupied entry and returns the previous one
+    #[inline]
+    pub fn replace(&mut self, value: V) -> Option<V> {
+        self.get_mut().map(|old| std::mem::replace(old, value))
+    }
+
+    #[inline]
+    pub fn get(&self) -> Option<&V> {
+        match self {
+            Self::Occupied(_, value) => Some(value),
+            Self::Vacant(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut V> {
+        match self {
+            Self::Occupied(_, value) => Some(value),
+            Self::Vacant(_) => None,
+        }
+    }
+
+    #[inline]
+    pub fn into_mut(self) -> Option<&'a mut V> {
+        match self {
+            Self::Occupied(_, value) => Some(value),
+            Self::Vacant(_) => None,
+        }
+    }
+}
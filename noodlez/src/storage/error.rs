@@ -0,0 +1,73 @@
+use std::fmt::{Debug, Display};
+
+use super::{
+    edge::{EdgeEnds, EdgeId},
+    node::NodeId,
+};
+
+pub enum GraphError<G> {
+    /// The node was never part of the graph
+    MissingNode(NodeId<G>),
+    /// The node was part of the graph but got removed
+    StaleNode(NodeId<G>),
+    /// The edge was never part of the graph
+    MissingEdge(EdgeId<G>),
+    /// The edge was part of the graph but got removed
+    StaleEdge(EdgeId<G>),
+    /// The graph doesn't allow parallel edges and there already is an edge between the ends
+    DuplicateEdge(EdgeEnds<G>),
+    /// The graph doesn't allow edges from a node to itself
+    SelfLoopRejected(NodeId<G>),
+}
+
+impl<G> Clone for GraphError<G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<G> Copy for GraphError<G> {}
+
+impl<G> Debug for GraphError<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingNode(id) => f.debug_tuple("MissingNode").field(id).finish(),
+            Self::StaleNode(id) => f.debug_tuple("StaleNode").field(id).finish(),
+            Self::MissingEdge(id) => f.debug_tuple("MissingEdge").field(id).finish(),
+            Self::StaleEdge(id) => f.debug_tuple("StaleEdge").field(id).finish(),
+            Self::DuplicateEdge(ends) => f.debug_tuple("DuplicateEdge").field(ends).finish(),
+            Self::SelfLoopRejected(id) => f.debug_tuple("SelfLoopRejected").field(id).finish(),
+        }
+    }
+}
+
+impl<G> Display for GraphError<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingNode(id) => write!(f, "node {id:?} was never part of the graph"),
+            Self::StaleNode(id) => write!(f, "node {id:?} was already removed from the graph"),
+            Self::MissingEdge(id) => write!(f, "edge {id:?} was never part of the graph"),
+            Self::StaleEdge(id) => write!(f, "edge {id:?} was already removed from the graph"),
+            Self::DuplicateEdge(ends) => write!(f, "there already is an edge {ends:?}"),
+            Self::SelfLoopRejected(id) => write!(f, "self loops on node {id:?} aren't allowed"),
+        }
+    }
+}
+
+impl<G> PartialEq for GraphError<G> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::MissingNode(a), Self::MissingNode(b))
+            | (Self::StaleNode(a), Self::StaleNode(b))
+            | (Self::SelfLoopRejected(a), Self::SelfLoopRejected(b)) => a == b,
+            (Self::MissingEdge(a), Self::MissingEdge(b))
+            | (Self::StaleEdge(a), Self::StaleEdge(b)) => a == b,
+            (Self::DuplicateEdge(a), Self::DuplicateEdge(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<G> Eq for GraphError<G> {}
+
+impl<G> std::error::Error for GraphError<G> {}
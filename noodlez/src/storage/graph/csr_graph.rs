@@ -0,0 +1,408 @@
+use std::marker::PhantomData;
+use std::ops::{Index, Range};
+
+use slotmap::SecondaryMap;
+
+use crate::storage::{
+    direction::{Directed, EdgeDirection},
+    edge::{EdgeEnds, EdgeId},
+    node::{InternalNodeID, NodeId},
+    traits::{GraphRead, GraphWrite},
+};
+
+use super::slotmap_graph::Graph;
+
+/// Compressed adjacency of all nodes. The neighbors of node `i` are stored in
+/// `targets[offsets[i]..offsets[i + 1]]` sorted by their index, the edges leading to them at the
+/// same positions in `edges`
+#[derive(Debug, Clone, Default)]
+struct Adjacency {
+    offsets: Vec<usize>,
+    targets: Vec<u32>,
+    edges: Vec<u32>,
+}
+
+impl Adjacency {
+    fn new(node_count: usize, mut entries: Vec<(u32, u32, u32)>) -> Self {
+        entries.sort_unstable();
+
+        let mut offsets = vec![0; node_count + 1];
+        entries
+            .iter()
+            .for_each(|(row, _, _)| offsets[*row as usize + 1] += 1);
+        (0..node_count).for_each(|i| offsets[i + 1] += offsets[i]);
+
+        let (targets, edges) = entries.into_iter().map(|(_, to, e)| (to, e)).unzip();
+        Self {
+            offsets,
+            targets,
+            edges,
+        }
+    }
+
+    #[inline]
+    fn row(&self, index: usize) -> Range<usize> {
+        self.offsets
+            .get(index..index + 2)
+            .map_or(0..0, |bounds| bounds[0]..bounds[1])
+    }
+
+    // all positions in the row of `from` which lead to `to`
+    #[inline]
+    fn between(&self, from: usize, to: u32) -> Range<usize> {
+        let row = self.row(from);
+        let targets = &self.targets[row.clone()];
+        let start = row.start + targets.partition_point(|t| *t < to);
+        let end = row.start + targets.partition_point(|t| *t <= to);
+        start..end
+    }
+}
+
+/// Immutable graph which stores its adjacency in contiguous arrays for fast traversals
+///
+/// Nodes and edges are addressed by dense indices in the order of the graph they were frozen
+/// from, so the ids of both graphs can be matched up by zipping their `nodes()` and `edges()`.
+#[derive(Debug, Clone)]
+pub struct CsrGraph<N, E, D = Directed> {
+    nodes: Vec<N>,
+    edges: Vec<E>,
+    edge_ends: Vec<EdgeEnds<Self>>,
+    outgoing: Adjacency,
+    // stays empty for undirected graphs since `outgoing` already contains both directions
+    incoming: Adjacency,
+    parallel_edges: bool,
+    self_loops: bool,
+    _direction: PhantomData<D>,
+}
+
+impl<N: Clone, E: Clone, D: EdgeDirection> From<&Graph<N, E, D>> for CsrGraph<N, E, D> {
+    fn from(graph: &Graph<N, E, D>) -> Self {
+        let mut indices = SecondaryMap::<InternalNodeID, u32>::new();
+        let nodes = graph
+            .nodes()
+            .enumerate()
+            .map(|(i, id)| {
+                indices.insert(id.get(), i as u32);
+                graph[id].clone()
+            })
+            .collect::<Vec<_>>();
+
+        let mut edges = Vec::with_capacity(graph.edge_count());
+        let mut edge_ends = Vec::with_capacity(graph.edge_count());
+        let mut outgoing = Vec::with_capacity(graph.edge_count());
+        let mut incoming = Vec::with_capacity(graph.edge_count());
+        graph.edges().enumerate().for_each(|(i, id)| {
+            let i = i as u32;
+            let EdgeEnds { from, to } = graph.edge_ends(id).copied().expect("edge is in graph");
+            let (from, to) = (indices[from.get()], indices[to.get()]);
+
+            edges.push(graph[id].clone());
+            edge_ends.push(EdgeEnds {
+                from: NodeId::from_slot(from),
+                to: NodeId::from_slot(to),
+            });
+            outgoing.push((from, to, i));
+            if D::DIRECTED {
+                incoming.push((to, from, i));
+            } else if from != to {
+                outgoing.push((to, from, i));
+            }
+        });
+
+        Self {
+            outgoing: Adjacency::new(nodes.len(), outgoing),
+            incoming: Adjacency::new(if D::DIRECTED { nodes.len() } else { 0 }, incoming),
+            nodes,
+            edges,
+            edge_ends,
+            parallel_edges: graph.allows_parallel_edges(),
+            self_loops: graph.allows_self_loops(),
+            _direction: PhantomData,
+        }
+    }
+}
+
+impl<N, E, D: EdgeDirection> CsrGraph<N, E, D> {
+    /// Turns the frozen graph back into a mutable one, keeping the order of nodes and edges
+    pub fn thaw(self) -> Graph<N, E, D> {
+        let mut graph = if self.parallel_edges {
+            Graph::multigraph()
+        } else {
+            Graph::new()
+        }
+        .with_self_loops(self.self_loops);
+
+        let ids = self
+            .nodes
+            .into_iter()
+            .map(|node| graph.add_node(node))
+            .collect::<Vec<_>>();
+        self.edges
+            .into_iter()
+            .zip(self.edge_ends)
+            .for_each(|(edge, EdgeEnds { from, to })| {
+                graph.add_edge(ids[from.slot() as usize], ids[to.slot() as usize], edge);
+            });
+
+        graph
+    }
+
+    #[inline]
+    fn node_index(&self, id: NodeId<Self>) -> Option<usize> {
+        let slot = id.slot();
+        ((slot as usize) < self.nodes.len() && id == NodeId::from_slot(slot))
+            .then_some(slot as usize)
+    }
+
+    #[inline]
+    fn edge_index(&self, id: EdgeId<Self>) -> Option<usize> {
+        let slot = id.slot();
+        ((slot as usize) < self.edges.len() && id == EdgeId::from_slot(slot))
+            .then_some(slot as usize)
+    }
+
+    #[inline]
+    fn adjacency(&self, outgoing: bool) -> &Adjacency {
+        if outgoing || !D::DIRECTED {
+            &self.outgoing
+        } else {
+            &self.incoming
+        }
+    }
+
+    #[inline]
+    fn adjacent(&self, id: NodeId<Self>, outgoing: bool) -> Range<usize> {
+        self.node_index(id)
+            .map_or(0..0, |index| self.adjacency(outgoing).row(index))
+    }
+
+    #[inline]
+    fn adjacent_nodes(
+        &self,
+        id: NodeId<Self>,
+        outgoing: bool,
+    ) -> impl Iterator<Item = NodeId<Self>> + '_ {
+        self.adjacency(outgoing).targets[self.adjacent(id, outgoing)]
+            .iter()
+            .copied()
+            .map(NodeId::from_slot)
+    }
+
+    #[inline]
+    fn adjacent_edges(
+        &self,
+        id: NodeId<Self>,
+        outgoing: bool,
+    ) -> impl Iterator<Item = EdgeId<Self>> + '_ {
+        self.adjacency(outgoing).edges[self.adjacent(id, outgoing)]
+            .iter()
+            .copied()
+            .map(EdgeId::from_slot)
+    }
+}
+
+impl<N, E, D: EdgeDirection> GraphRead<N, E> for CsrGraph<N, E, D> {
+    type Direction = D;
+
+    #[inline]
+    fn node(&self, id: NodeId<Self>) -> Option<&N> {
+        self.node_index(id).map(|index| &self.nodes[index])
+    }
+
+    #[inline]
+    fn nodes(&self) -> impl Iterator<Item = NodeId<Self>> {
+        (0..self.nodes.len() as u32).map(NodeId::from_slot)
+    }
+
+    #[inline]
+    fn incoming_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
+        self.adjacent_nodes(id, false)
+    }
+
+    #[inline]
+    fn outcoming_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
+        self.adjacent_nodes(id, true)
+    }
+
+    #[inline]
+    fn neighbor_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
+        D::DIRECTED
+            .then(|| self.incoming_nodes(id))
+            .into_iter()
+            .flatten()
+            .chain(self.outcoming_nodes(id))
+    }
+
+    #[inline]
+    fn contains_node(&self, id: NodeId<Self>) -> bool {
+        self.node_index(id).is_some()
+    }
+
+    #[inline]
+    fn has_edge_between(&self, edge_ends: EdgeEnds<Self>) -> bool {
+        self.edges_between(edge_ends).next().is_some()
+    }
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn edge(&self, id: EdgeId<Self>) -> Option<&E> {
+        self.edge_index(id).map(|index| &self.edges[index])
+    }
+
+    #[inline]
+    fn edges(&self) -> impl Iterator<Item = EdgeId<Self>> {
+        (0..self.edges.len() as u32).map(EdgeId::from_slot)
+    }
+
+    #[inline]
+    fn edge_between(&self, edge_ends: EdgeEnds<Self>) -> Option<EdgeId<Self>> {
+        self.edges_between(edge_ends).next()
+    }
+
+    #[inline]
+    fn edges_between(&self, edge_ends: EdgeEnds<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        let range = match (
+            self.node_index(edge_ends.from),
+            self.node_index(edge_ends.to),
+        ) {
+            (Some(from), Some(to)) => self.outgoing.between(from, to as u32),
+            _ => 0..0,
+        };
+        self.outgoing.edges[range]
+            .iter()
+            .copied()
+            .map(EdgeId::from_slot)
+    }
+
+    #[inline]
+    fn edge_ends(&self, id: EdgeId<Self>) -> Option<&EdgeEnds<Self>> {
+        self.edge_index(id).map(|index| &self.edge_ends[index])
+    }
+
+    #[inline]
+    fn incoming_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        self.adjacent_edges(id, false)
+    }
+
+    #[inline]
+    fn outcoming_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        self.adjacent_edges(id, true)
+    }
+
+    #[inline]
+    fn neighbor_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        D::DIRECTED
+            .then(|| self.incoming_edges(id))
+            .into_iter()
+            .flatten()
+            .chain(self.outcoming_edges(id))
+    }
+
+    #[inline]
+    fn contains_edge(&self, id: EdgeId<Self>) -> bool {
+        self.edge_index(id).is_some()
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<N, E, D: EdgeDirection> Index<NodeId<Self>> for CsrGraph<N, E, D> {
+    type Output = N;
+
+    #[inline]
+    fn index(&self, id: NodeId<Self>) -> &Self::Output {
+        self.node(id)
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
+    }
+}
+
+impl<N, E, D: EdgeDirection> Index<EdgeId<Self>> for CsrGraph<N, E, D> {
+    type Output = E;
+
+    #[inline]
+    fn index(&self, id: EdgeId<Self>) -> &Self::Output {
+        self.edge(id)
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // weights of every node and its outgoing neighbors, in graph order
+    fn outgoing(graph: &CsrGraph<&'static str, u32>) -> Vec<(&'static str, Vec<&'static str>)> {
+        graph
+            .nodes()
+            .map(|id| {
+                let neighbors = graph.outcoming_nodes(id).map(|to| graph[to]);
+                (graph[id], neighbors.collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn freeze_and_thaw() {
+        let mut graph = Graph::<_, _>::multigraph();
+        let [a, b, c, d] = ["a", "b", "c", "d"].map(|node| graph.add_node(node));
+        for (from, to, edge) in [(a, b, 1), (a, b, 2), (b, c, 3), (c, a, 4), (d, d, 5)] {
+            graph.add_edge(from, to, edge);
+        }
+        let removed = graph.add_edge(c, d, 6).unwrap();
+        graph.add_edge(d, c, 7);
+        graph.add_edge(d, a, 8);
+        // leaves stale slots behind, the new node takes over the slot of b
+        graph.remove_node(b);
+        graph.remove_edge(removed);
+        let e = graph.add_node("e");
+        graph.add_edge(e, a, 9);
+
+        let frozen = graph.freeze();
+        assert_eq!(frozen.node_count(), 4);
+        assert_eq!(frozen.edge_count(), 5);
+        assert!(frozen
+            .nodes()
+            .map(|id| frozen[id])
+            .eq(graph.nodes().map(|id| graph[id])));
+        assert!(frozen
+            .edges()
+            .map(|id| frozen[id])
+            .eq(graph.edges().map(|id| graph[id])));
+
+        // neighbors are sorted by their position in the frozen graph
+        let expected = [
+            ("a", vec![]),
+            ("e", vec!["a"]),
+            ("c", vec!["a"]),
+            ("d", vec!["a", "c", "d"]),
+        ];
+        assert_eq!(outgoing(&frozen), expected);
+        let [a, _, c, d] = [0, 1, 2, 3].map(NodeId::from_slot);
+        assert!(frozen.incoming_nodes(a).eq([NodeId::from_slot(1), c, d]));
+        assert!(frozen
+            .edges_between(EdgeEnds { from: d, to: d })
+            .map(|id| frozen[id])
+            .eq([5]));
+
+        let thawed = frozen.clone().thaw();
+        assert!(thawed.allows_parallel_edges());
+        assert_eq!(thawed.node_count(), 4);
+        assert_eq!(thawed.edge_count(), 5);
+        assert!(thawed
+            .nodes()
+            .map(|id| thawed[id])
+            .eq(frozen.nodes().map(|id| frozen[id])));
+        assert!(thawed
+            .edges()
+            .map(|id| thawed[id])
+            .eq(frozen.edges().map(|id| frozen[id])));
+        assert_eq!(outgoing(&thawed.freeze()), expected);
+    }
+}
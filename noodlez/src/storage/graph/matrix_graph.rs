@@ -0,0 +1,437 @@
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+use slotmap::{SecondaryMap, SlotMap};
+
+use crate::storage::{
+    direction::{Directed, EdgeDirection},
+    edge::{EdgeEnds, EdgeId, InternalEdgeID},
+    error::GraphError,
+    node::{InternalNodeID, NodeId},
+    traits::{GraphRead, GraphWrite},
+};
+
+use super::SlotVersions;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Graph which stores its adjacency in a matrix over the slots of its nodes
+///
+/// Looking up edges between two nodes doesn't involve any hashing, at the cost of memory that
+/// grows quadratically with the number of nodes. This makes it a good fit for small dense graphs.
+/// Parallel edges aren't supported.
+#[derive(Debug, Clone)]
+pub struct MatrixGraph<N, E, D = Directed> {
+    nodes: SlotMap<InternalNodeID, N>,
+    edges: SlotMap<InternalEdgeID, E>,
+    edge_ends: SecondaryMap<InternalEdgeID, EdgeEnds<Self>>,
+    // number of rows and columns of the matrices below
+    capacity: usize,
+    // row major bit matrix, every row is padded to whole words
+    adjacency: Vec<u64>,
+    // row major matrix of the edges connecting two nodes
+    cells: Vec<Option<InternalEdgeID>>,
+    node_versions: SlotVersions,
+    edge_versions: SlotVersions,
+    self_loops: bool,
+    _direction: PhantomData<D>,
+}
+
+impl<N, E, D> Default for MatrixGraph<N, E, D> {
+    fn default() -> Self {
+        Self {
+            nodes: SlotMap::with_key(),
+            edges: SlotMap::with_key(),
+            edge_ends: SecondaryMap::new(),
+            capacity: 0,
+            adjacency: Vec::new(),
+            cells: Vec::new(),
+            node_versions: SlotVersions::default(),
+            edge_versions: SlotVersions::default(),
+            self_loops: true,
+            _direction: PhantomData,
+        }
+    }
+}
+
+impl<N, E, D: EdgeDirection> MatrixGraph<N, E, D> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a graph which can hold `capacity` nodes before its matrix has to grow
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut graph = Self::default();
+        graph.grow(capacity);
+        graph
+    }
+
+    /// Sets whether edges from a node to itself can be added
+    #[inline]
+    pub fn with_self_loops(mut self, allowed: bool) -> Self {
+        self.self_loops = allowed;
+        self
+    }
+
+    #[inline]
+    pub fn allows_self_loops(&self) -> bool {
+        self.self_loops
+    }
+
+    // an unknown id can only stem from a removed element if its version was already handed out
+    // for its slot, ids of other graphs may point to the same slot with any version
+    #[inline]
+    fn node_error(&self, id: NodeId<Self>) -> GraphError<Self> {
+        if self.node_versions.handed_out(id.slot(), id.version()) {
+            GraphError::StaleNode(id)
+        } else {
+            GraphError::MissingNode(id)
+        }
+    }
+
+    #[inline]
+    fn edge_error(&self, id: EdgeId<Self>) -> GraphError<Self> {
+        if self.edge_versions.handed_out(id.slot(), id.version()) {
+            GraphError::StaleEdge(id)
+        } else {
+            GraphError::MissingEdge(id)
+        }
+    }
+
+    #[inline]
+    fn words_per_row(&self) -> usize {
+        self.capacity.div_ceil(WORD_BITS)
+    }
+
+    fn grow(&mut self, min_capacity: usize) {
+        if min_capacity <= self.capacity {
+            return;
+        }
+
+        let capacity = min_capacity.max(self.capacity * 2);
+        let words = capacity.div_ceil(WORD_BITS);
+        let mut adjacency = vec![0; capacity * words];
+        let mut cells = vec![None; capacity * capacity];
+        (0..self.capacity).for_each(|row| {
+            let old_words = self.words_per_row();
+            adjacency[row * words..row * words + old_words]
+                .copy_from_slice(&self.adjacency[row * old_words..(row + 1) * old_words]);
+            cells[row * capacity..row * capacity + self.capacity]
+                .copy_from_slice(&self.cells[row * self.capacity..(row + 1) * self.capacity]);
+        });
+
+        self.capacity = capacity;
+        self.adjacency = adjacency;
+        self.cells = cells;
+    }
+
+    #[inline]
+    fn bit(&self, row: usize, column: usize) -> bool {
+        let word = self.adjacency[row * self.words_per_row() + column / WORD_BITS];
+        word & (1 << (column % WORD_BITS)) != 0
+    }
+
+    #[inline]
+    fn set(&mut self, row: usize, column: usize, edge: Option<InternalEdgeID>) {
+        let words = self.words_per_row();
+        let word = &mut self.adjacency[row * words + column / WORD_BITS];
+        if edge.is_some() {
+            *word |= 1 << (column % WORD_BITS);
+        } else {
+            *word &= !(1 << (column % WORD_BITS));
+        }
+        self.cells[row * self.capacity + column] = edge;
+    }
+
+    // matrix position of the edge between both ends, as long as both of them are in the graph
+    #[inline]
+    fn position(&self, ends: EdgeEnds<Self>) -> Option<(usize, usize)> {
+        (self.contains_node(ends.from) && self.contains_node(ends.to))
+            .then(|| (ends.from.slot() as usize, ends.to.slot() as usize))
+    }
+
+    #[inline]
+    fn cell(&self, row: usize, column: usize) -> Option<InternalEdgeID> {
+        self.cells[row * self.capacity + column]
+    }
+
+    // edges in the cells of the node, for undirected graphs the rows are symmetric so everything
+    // can be found in the row of the node, incoming edges of directed graphs fill its column
+    #[inline]
+    fn adjacent(
+        &self,
+        id: NodeId<Self>,
+        outgoing: bool,
+    ) -> impl Iterator<Item = InternalEdgeID> + '_ {
+        let slot = self.contains_node(id).then_some(id.slot() as usize);
+        let by_row = slot.filter(|_| outgoing || !D::DIRECTED).map(|row| {
+            self.cells[row * self.capacity..(row + 1) * self.capacity]
+                .iter()
+                .flatten()
+        });
+        let by_column = slot
+            .filter(|_| !outgoing && D::DIRECTED)
+            .map(|column| self.cells[column..].iter().step_by(self.capacity).flatten());
+        by_row
+            .into_iter()
+            .flatten()
+            .chain(by_column.into_iter().flatten())
+            .copied()
+    }
+
+    // the other ends of the adjacent edges, their ids already contain the right version
+    #[inline]
+    fn adjacent_nodes(
+        &self,
+        id: NodeId<Self>,
+        outgoing: bool,
+    ) -> impl Iterator<Item = NodeId<Self>> + '_ {
+        self.adjacent(id, outgoing)
+            .filter_map(|edge| self.edge_ends.get(edge))
+            .map(move |ends| match (D::DIRECTED, outgoing) {
+                (true, true) => ends.to,
+                (true, false) => ends.from,
+                (false, _) if ends.from == id => ends.to,
+                (false, _) => ends.from,
+            })
+    }
+}
+
+impl<N, E, D: EdgeDirection> GraphRead<N, E> for MatrixGraph<N, E, D> {
+    type Direction = D;
+
+    #[inline]
+    fn node(&self, id: NodeId<Self>) -> Option<&N> {
+        self.nodes.get(id.get())
+    }
+
+    #[inline]
+    fn try_node(&self, id: NodeId<Self>) -> Result<&N, GraphError<Self>> {
+        self.nodes.get(id.get()).ok_or_else(|| self.node_error(id))
+    }
+
+    #[inline]
+    fn nodes(&self) -> impl Iterator<Item = NodeId<Self>> {
+        self.nodes.keys().map(NodeId::new)
+    }
+
+    #[inline]
+    fn incoming_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
+        self.adjacent_nodes(id, false)
+    }
+
+    #[inline]
+    fn outcoming_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
+        self.adjacent_nodes(id, true)
+    }
+
+    #[inline]
+    fn neighbor_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
+        D::DIRECTED
+            .then(|| self.incoming_nodes(id))
+            .into_iter()
+            .flatten()
+            .chain(self.outcoming_nodes(id))
+    }
+
+    #[inline]
+    fn contains_node(&self, id: NodeId<Self>) -> bool {
+        self.nodes.contains_key(id.get())
+    }
+
+    #[inline]
+    fn has_edge_between(&self, edge_ends: EdgeEnds<Self>) -> bool {
+        self.position(edge_ends)
+            .is_some_and(|(row, column)| self.bit(row, column))
+    }
+
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    fn edge(&self, id: EdgeId<Self>) -> Option<&E> {
+        self.edges.get(id.get())
+    }
+
+    #[inline]
+    fn try_edge(&self, id: EdgeId<Self>) -> Result<&E, GraphError<Self>> {
+        self.edges.get(id.get()).ok_or_else(|| self.edge_error(id))
+    }
+
+    #[inline]
+    fn edges(&self) -> impl Iterator<Item = EdgeId<Self>> {
+        self.edges.keys().map(EdgeId::new)
+    }
+
+    #[inline]
+    fn edge_between(&self, edge_ends: EdgeEnds<Self>) -> Option<EdgeId<Self>> {
+        self.position(edge_ends)
+            .and_then(|(row, column)| self.cell(row, column))
+            .map(EdgeId::new)
+    }
+
+    #[inline]
+    fn edge_ends(&self, id: EdgeId<Self>) -> Option<&EdgeEnds<Self>> {
+        self.edge_ends.get(id.get())
+    }
+
+    #[inline]
+    fn incoming_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        self.adjacent(id, false).map(EdgeId::new)
+    }
+
+    #[inline]
+    fn outcoming_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        self.adjacent(id, true).map(EdgeId::new)
+    }
+
+    #[inline]
+    fn neighbor_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        D::DIRECTED
+            .then(|| self.incoming_edges(id))
+            .into_iter()
+            .flatten()
+            .chain(self.outcoming_edges(id))
+    }
+
+    #[inline]
+    fn contains_edge(&self, id: EdgeId<Self>) -> bool {
+        self.edges.contains_key(id.get())
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<N, E, D: EdgeDirection> GraphWrite<N, E> for MatrixGraph<N, E, D> {
+    #[inline]
+    fn add_node(&mut self, node: N) -> NodeId<Self> {
+        let id = NodeId::new(self.nodes.insert(node));
+        self.node_versions.hand_out(id.slot(), id.version());
+        self.grow(id.slot() as usize + 1);
+        id
+    }
+
+    #[inline]
+    fn try_update_node(&mut self, id: NodeId<Self>, node: N) -> Result<N, GraphError<Self>> {
+        match self.nodes.get_mut(id.get()) {
+            Some(old) => Ok(std::mem::replace(old, node)),
+            None => Err(self.node_error(id)),
+        }
+    }
+
+    #[inline]
+    fn try_remove_node(&mut self, id: NodeId<Self>) -> Result<N, GraphError<Self>> {
+        self.disconnect_node(id);
+        self.nodes
+            .remove(id.get())
+            .ok_or_else(|| self.node_error(id))
+    }
+
+    #[inline]
+    fn node_mut(&mut self, id: NodeId<Self>) -> Option<&mut N> {
+        self.nodes.get_mut(id.get())
+    }
+
+    fn try_add_edge(
+        &mut self,
+        from: NodeId<Self>,
+        to: NodeId<Self>,
+        edge: E,
+    ) -> Result<EdgeId<Self>, GraphError<Self>> {
+        self.try_node(from)?;
+        self.try_node(to)?;
+
+        if !self.self_loops && from == to {
+            return Err(GraphError::SelfLoopRejected(from));
+        }
+
+        let edge_ends = EdgeEnds { from, to };
+        if self.has_edge_between(edge_ends) {
+            return Err(GraphError::DuplicateEdge(edge_ends));
+        }
+
+        let id = EdgeId::new(self.edges.insert(edge));
+        self.edge_versions.hand_out(id.slot(), id.version());
+        self.edge_ends.insert(id.get(), edge_ends);
+
+        let (row, column) = (from.slot() as usize, to.slot() as usize);
+        self.set(row, column, Some(id.get()));
+        if !D::DIRECTED {
+            self.set(column, row, Some(id.get()));
+        }
+
+        Ok(id)
+    }
+
+    #[inline]
+    fn try_update_edge(&mut self, id: EdgeId<Self>, edge: E) -> Result<E, GraphError<Self>> {
+        match self.edges.get_mut(id.get()) {
+            Some(old) => Ok(std::mem::replace(old, edge)),
+            None => Err(self.edge_error(id)),
+        }
+    }
+
+    fn try_remove_edge(&mut self, id: EdgeId<Self>) -> Result<E, GraphError<Self>> {
+        if let Some(EdgeEnds { from, to }) = self.edge_ends.remove(id.get()) {
+            let (row, column) = (from.slot() as usize, to.slot() as usize);
+            self.set(row, column, None);
+            if !D::DIRECTED {
+                self.set(column, row, None);
+            }
+        }
+        self.edges
+            .remove(id.get())
+            .ok_or_else(|| self.edge_error(id))
+    }
+
+    #[inline]
+    fn edge_mut(&mut self, id: EdgeId<Self>) -> Option<&mut E> {
+        self.edges.get_mut(id.get())
+    }
+}
+
+impl<N, E, D: EdgeDirection> Index<NodeId<Self>> for MatrixGraph<N, E, D> {
+    type Output = N;
+
+    #[inline]
+    fn index(&self, id: NodeId<Self>) -> &Self::Output {
+        self.nodes
+            .get(id.get())
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
+    }
+}
+
+impl<N, E, D: EdgeDirection> IndexMut<NodeId<Self>> for MatrixGraph<N, E, D> {
+    #[inline]
+    fn index_mut(&mut self, id: NodeId<Self>) -> &mut Self::Output {
+        self.nodes
+            .get_mut(id.get())
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
+    }
+}
+
+impl<N, E, D: EdgeDirection> Index<EdgeId<Self>> for MatrixGraph<N, E, D> {
+    type Output = E;
+
+    #[inline]
+    fn index(&self, id: EdgeId<Self>) -> &Self::Output {
+        self.edges
+            .get(id.get())
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
+    }
+}
+
+impl<N, E, D: EdgeDirection> IndexMut<EdgeId<Self>> for MatrixGraph<N, E, D> {
+    #[inline]
+    fn index_mut(&mut self, id: EdgeId<Self>) -> &mut Self::Output {
+        self.edges
+            .get_mut(id.get())
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
+    }
+}
@@ -1 +1,27 @@
+pub mod csr_graph;
+pub mod matrix_graph;
 pub mod slotmap_graph;
+
+// latest version a slotmap handed out for each of its slots. Versions of a slot only ever grow,
+// so ids with an older version were removed, while ids with a newer version or an unknown slot
+// were never part of the slotmap.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SlotVersions(Vec<u32>);
+
+impl SlotVersions {
+    #[inline]
+    pub(crate) fn hand_out(&mut self, slot: u32, version: u32) {
+        let slot = slot as usize;
+        if slot >= self.0.len() {
+            self.0.resize(slot + 1, 0);
+        }
+        self.0[slot] = version;
+    }
+
+    #[inline]
+    pub(crate) fn handed_out(&self, slot: u32, version: u32) -> bool {
+        self.0
+            .get(slot as usize)
+            .is_some_and(|latest| version <= *latest)
+    }
+}
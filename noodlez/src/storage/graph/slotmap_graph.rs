@@ -1,61 +1,191 @@
 use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
 
 use slotmap::{SecondaryMap, SlotMap};
 
 use crate::storage::{
+    direction::{Directed, EdgeDirection},
     edge::{EdgeEnds, EdgeId, InternalEdgeID},
+    error::GraphError,
     node::{InternalNodeID, NodeId},
-    traits::GraphStorage,
+    traits::{GraphRead, GraphWrite},
 };
 
+use super::{csr_graph::CsrGraph, SlotVersions};
+
 #[derive(Debug, Clone)]
-pub struct Graph<N, E> {
+pub struct Graph<N, E, D = Directed> {
     nodes: SlotMap<InternalNodeID, N>,
     edges: SlotMap<InternalEdgeID, E>,
     edge_ends: SecondaryMap<InternalEdgeID, EdgeEnds<Self>>,
-    node_to_edges: HashMap<EdgeEnds<Self>, InternalEdgeID>,
-    incoming_nodes: SecondaryMap<InternalNodeID, Vec<InternalNodeID>>,
-    outgoing_nodes: SecondaryMap<InternalNodeID, Vec<InternalNodeID>>,
+    // positions of the edge in the outgoing edges of its source and the incoming edges of its
+    // target, used to swap remove it from both lists in constant time
+    edge_positions: SecondaryMap<InternalEdgeID, (usize, usize)>,
+    node_to_edges: HashMap<EdgeEnds<Self>, Vec<InternalEdgeID>>,
+    // adjacent edges of every node together with the node on their other end
+    incoming_edges: SecondaryMap<InternalNodeID, Vec<(InternalEdgeID, InternalNodeID)>>,
+    outgoing_edges: SecondaryMap<InternalNodeID, Vec<(InternalEdgeID, InternalNodeID)>>,
+    node_versions: SlotVersions,
+    edge_versions: SlotVersions,
+    parallel_edges: bool,
+    self_loops: bool,
+    _direction: PhantomData<D>,
 }
 
-impl<N, E> Default for Graph<N, E> {
+impl<N, E, D> Default for Graph<N, E, D> {
     fn default() -> Self {
         Self {
             nodes: SlotMap::with_key(),
             edges: SlotMap::with_key(),
             edge_ends: SecondaryMap::new(),
+            edge_positions: SecondaryMap::new(),
             node_to_edges: HashMap::new(),
-            incoming_nodes: SecondaryMap::new(),
-            outgoing_nodes: SecondaryMap::new(),
+            incoming_edges: SecondaryMap::new(),
+            outgoing_edges: SecondaryMap::new(),
+            node_versions: SlotVersions::default(),
+            edge_versions: SlotVersions::default(),
+            parallel_edges: false,
+            self_loops: true,
+            _direction: PhantomData,
         }
     }
 }
 
-impl<N, E> GraphStorage<N, E> for Graph<N, E> {
+impl<N, E, D: EdgeDirection> Graph<N, E, D> {
+    /// Creates a graph which allows at most one edge per pair of nodes
     #[inline]
-    fn add_node(&mut self, node: N) -> NodeId<Self> {
-        let id = self.nodes.insert(node);
-        NodeId::new(id)
+    pub fn new() -> Self {
+        Self::default()
     }
 
+    /// Creates a graph which allows parallel edges between the same pair of nodes
     #[inline]
-    fn update_node(&mut self, id: NodeId<Self>, node: N) {
-        if let Some(old) = self.nodes.get_mut(id.get()) {
-            *old = node;
+    pub fn multigraph() -> Self {
+        Self {
+            parallel_edges: true,
+            ..Self::default()
         }
     }
 
+    /// Sets whether edges from a node to itself can be added
     #[inline]
-    fn remove_node(&mut self, id: NodeId<Self>) -> Option<N> {
-        self.disconnect_node(id);
-        self.nodes.remove(id.get())
+    pub fn with_self_loops(mut self, allowed: bool) -> Self {
+        self.self_loops = allowed;
+        self
+    }
+
+    #[inline]
+    pub fn allows_parallel_edges(&self) -> bool {
+        self.parallel_edges
+    }
+
+    #[inline]
+    pub fn allows_self_loops(&self) -> bool {
+        self.self_loops
+    }
+
+    /// Creates an immutable copy of the graph which is optimized for traversals
+    pub fn freeze(&self) -> CsrGraph<N, E, D>
+    where
+        N: Clone,
+        E: Clone,
+    {
+        CsrGraph::from(self)
+    }
+
+    // an unknown id can only stem from a removed element if its version was already handed out
+    // for its slot, ids of other graphs may point to the same slot with any version
+    #[inline]
+    fn node_error(&self, id: NodeId<Self>) -> GraphError<Self> {
+        if self.node_versions.handed_out(id.slot(), id.version()) {
+            GraphError::StaleNode(id)
+        } else {
+            GraphError::MissingNode(id)
+        }
+    }
+
+    #[inline]
+    fn edge_error(&self, id: EdgeId<Self>) -> GraphError<Self> {
+        if self.edge_versions.handed_out(id.slot(), id.version()) {
+            GraphError::StaleEdge(id)
+        } else {
+            GraphError::MissingEdge(id)
+        }
+    }
+
+    // undirected edges are looked up independent of the order of their ends
+    #[inline]
+    fn lookup_key(ends: EdgeEnds<Self>) -> EdgeEnds<Self> {
+        if D::DIRECTED || ends.from.get() <= ends.to.get() {
+            ends
+        } else {
+            EdgeEnds {
+                from: ends.to,
+                to: ends.from,
+            }
+        }
+    }
+
+    // in the undirected case every edge leaves and enters both of its ends, self loops are only
+    // reported once though
+    #[inline]
+    fn adjacent(
+        &self,
+        id: NodeId<Self>,
+        outgoing: bool,
+    ) -> impl Iterator<Item = (InternalEdgeID, InternalNodeID)> + '_ {
+        let (primary, secondary) = if outgoing {
+            (&self.outgoing_edges, &self.incoming_edges)
+        } else {
+            (&self.incoming_edges, &self.outgoing_edges)
+        };
+        let secondary = (!D::DIRECTED)
+            .then(|| secondary.get(id.get()))
+            .flatten()
+            .into_iter()
+            .flatten()
+            .filter(move |(_, n)| *n != id.get());
+        primary
+            .get(id.get())
+            .into_iter()
+            .flatten()
+            .chain(secondary)
+            .copied()
+    }
+
+    #[inline]
+    fn adjacent_nodes(
+        &self,
+        id: NodeId<Self>,
+        outgoing: bool,
+    ) -> impl Iterator<Item = NodeId<Self>> + '_ {
+        self.adjacent(id, outgoing).map(|(_, n)| NodeId::new(n))
     }
 
+    #[inline]
+    fn adjacent_edges(
+        &self,
+        id: NodeId<Self>,
+        outgoing: bool,
+    ) -> impl Iterator<Item = EdgeId<Self>> + '_ {
+        self.adjacent(id, outgoing).map(|(e, _)| EdgeId::new(e))
+    }
+}
+
+impl<N, E, D: EdgeDirection> GraphRead<N, E> for Graph<N, E, D> {
+    type Direction = D;
+
     #[inline]
     fn node(&self, id: NodeId<Self>) -> Option<&N> {
         self.nodes.get(id.get())
     }
 
+    #[inline]
+    fn try_node(&self, id: NodeId<Self>) -> Result<&N, GraphError<Self>> {
+        self.nodes.get(id.get()).ok_or_else(|| self.node_error(id))
+    }
+
     #[inline]
     fn nodes(&self) -> impl Iterator<Item = NodeId<Self>> {
         self.nodes.keys().map(NodeId::new)
@@ -63,22 +193,21 @@ impl<N, E> GraphStorage<N, E> for Graph<N, E> {
 
     #[inline]
     fn incoming_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
-        self.incoming_nodes
-            .get(id.get())
-            .into_iter()
-            .flatten()
-            .copied()
-            .map(NodeId::new)
+        self.adjacent_nodes(id, false)
     }
 
     #[inline]
     fn outcoming_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
-        self.outgoing_nodes
-            .get(id.get())
+        self.adjacent_nodes(id, true)
+    }
+
+    #[inline]
+    fn neighbor_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>> {
+        D::DIRECTED
+            .then(|| self.incoming_nodes(id))
             .into_iter()
             .flatten()
-            .copied()
-            .map(NodeId::new)
+            .chain(self.outcoming_nodes(id))
     }
 
     #[inline]
@@ -88,7 +217,7 @@ impl<N, E> GraphStorage<N, E> for Graph<N, E> {
 
     #[inline]
     fn has_edge_between(&self, ends: EdgeEnds<Self>) -> bool {
-        self.node_to_edges.contains_key(&ends)
+        self.node_to_edges.contains_key(&Self::lookup_key(ends))
     }
 
     #[inline]
@@ -96,78 +225,471 @@ impl<N, E> GraphStorage<N, E> for Graph<N, E> {
         self.nodes.len()
     }
 
-    fn add_edge(&mut self, from: NodeId<Self>, to: NodeId<Self>, edge: E) -> Option<EdgeId<Self>> {
-        if !(self.nodes.contains_key(from.get()) && self.nodes.contains_key(to.get())) {
-            return None;
+    #[inline]
+    fn edge(&self, id: EdgeId<Self>) -> Option<&E> {
+        self.edges.get(id.get())
+    }
+
+    #[inline]
+    fn try_edge(&self, id: EdgeId<Self>) -> Result<&E, GraphError<Self>> {
+        self.edges.get(id.get()).ok_or_else(|| self.edge_error(id))
+    }
+
+    #[inline]
+    fn edges(&self) -> impl Iterator<Item = EdgeId<Self>> {
+        self.edges.keys().map(EdgeId::new)
+    }
+
+    #[inline]
+    fn edge_between(&self, edge_ends: EdgeEnds<Self>) -> Option<EdgeId<Self>> {
+        self.edges_between(edge_ends).next()
+    }
+
+    #[inline]
+    fn edges_between(&self, edge_ends: EdgeEnds<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        self.node_to_edges
+            .get(&Self::lookup_key(edge_ends))
+            .into_iter()
+            .flatten()
+            .copied()
+            .map(EdgeId::new)
+    }
+
+    #[inline]
+    fn edge_ends(&self, id: EdgeId<Self>) -> Option<&EdgeEnds<Self>> {
+        self.edge_ends.get(id.get())
+    }
+
+    #[inline]
+    fn incoming_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        self.adjacent_edges(id, false)
+    }
+
+    #[inline]
+    fn outcoming_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        self.adjacent_edges(id, true)
+    }
+
+    #[inline]
+    fn neighbor_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        D::DIRECTED
+            .then(|| self.incoming_edges(id))
+            .into_iter()
+            .flatten()
+            .chain(self.outcoming_edges(id))
+    }
+
+    #[inline]
+    fn contains_edge(&self, id: EdgeId<Self>) -> bool {
+        self.edges.contains_key(id.get())
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+impl<N, E, D: EdgeDirection> GraphWrite<N, E> for Graph<N, E, D> {
+    #[inline]
+    fn add_node(&mut self, node: N) -> NodeId<Self> {
+        let id = NodeId::new(self.nodes.insert(node));
+        self.node_versions.hand_out(id.slot(), id.version());
+        id
+    }
+
+    #[inline]
+    fn try_update_node(&mut self, id: NodeId<Self>, node: N) -> Result<N, GraphError<Self>> {
+        match self.nodes.get_mut(id.get()) {
+            Some(old) => Ok(std::mem::replace(old, node)),
+            None => Err(self.node_error(id)),
         }
+    }
 
-        let out_n = self.outgoing_nodes.entry(from.get())?.or_default();
-        let in_n = self.incoming_nodes.entry(to.get())?.or_default();
+    #[inline]
+    fn try_remove_node(&mut self, id: NodeId<Self>) -> Result<N, GraphError<Self>> {
+        self.disconnect_node(id);
+        self.outgoing_edges.remove(id.get());
+        self.incoming_edges.remove(id.get());
+        self.nodes
+            .remove(id.get())
+            .ok_or_else(|| self.node_error(id))
+    }
 
-        out_n.push(to.get());
-        in_n.push(from.get());
+    #[inline]
+    fn node_mut(&mut self, id: NodeId<Self>) -> Option<&mut N> {
+        self.nodes.get_mut(id.get())
+    }
+
+    fn try_add_edge(
+        &mut self,
+        from: NodeId<Self>,
+        to: NodeId<Self>,
+        edge: E,
+    ) -> Result<EdgeId<Self>, GraphError<Self>> {
+        self.try_node(from)?;
+        self.try_node(to)?;
+
+        if !self.self_loops && from == to {
+            return Err(GraphError::SelfLoopRejected(from));
+        }
 
-        let id = self.edges.insert(edge);
         let edge_ends = EdgeEnds { from, to };
-        self.edge_ends.insert(id, edge_ends);
-        self.node_to_edges.insert(edge_ends, id);
+        if !self.parallel_edges && self.has_edge_between(edge_ends) {
+            return Err(GraphError::DuplicateEdge(edge_ends));
+        }
 
-        Some(EdgeId::new(id))
+        // both ends are known to be part of the graph, so the entries can only be missing for
+        // outdated versions of the ids
+        let out_e = self
+            .outgoing_edges
+            .entry(from.get())
+            .ok_or(GraphError::StaleNode(from))?
+            .or_default();
+        let in_e = self
+            .incoming_edges
+            .entry(to.get())
+            .ok_or(GraphError::StaleNode(to))?
+            .or_default();
+
+        let id = EdgeId::new(self.edges.insert(edge));
+        self.edge_positions
+            .insert(id.get(), (out_e.len(), in_e.len()));
+        out_e.push((id.get(), to.get()));
+        in_e.push((id.get(), from.get()));
+
+        self.edge_versions.hand_out(id.slot(), id.version());
+        self.edge_ends.insert(id.get(), edge_ends);
+        self.node_to_edges
+            .entry(Self::lookup_key(edge_ends))
+            .or_default()
+            .push(id.get());
+
+        Ok(id)
     }
 
     #[inline]
-    fn update_edge(&mut self, id: EdgeId<Self>, edge: E) {
-        if let Some(old) = self.edges.get_mut(id.get()) {
-            *old = edge;
+    fn try_update_edge(&mut self, id: EdgeId<Self>, edge: E) -> Result<E, GraphError<Self>> {
+        match self.edges.get_mut(id.get()) {
+            Some(old) => Ok(std::mem::replace(old, edge)),
+            None => Err(self.edge_error(id)),
         }
     }
 
-    fn remove_edge(&mut self, id: EdgeId<Self>) -> Option<E> {
-        if let Some(edge_ends @ EdgeEnds { from, to }) = self.edge_ends.get(id.get()) {
-            if let Some(nodes) = self.incoming_nodes.get_mut(to.get()) {
-                let id = from.get();
-                nodes.retain(|e| e != &id);
-            }
+    fn try_remove_edge(&mut self, id: EdgeId<Self>) -> Result<E, GraphError<Self>> {
+        if let Some(edge_ends @ EdgeEnds { from, to }) = self.edge_ends.remove(id.get()) {
+            if let Some((out_pos, in_pos)) = self.edge_positions.remove(id.get()) {
+                if let Some(edges) = self.outgoing_edges.get_mut(from.get()) {
+                    edges.swap_remove(out_pos);
+                    if let Some((moved, _)) = edges.get(out_pos) {
+                        self.edge_positions[*moved].0 = out_pos;
+                    }
+                }
 
-            if let Some(nodes) = self.outgoing_nodes.get_mut(from.get()) {
-                let id = to.get();
-                nodes.retain(|e| e != &id);
+                if let Some(edges) = self.incoming_edges.get_mut(to.get()) {
+                    edges.swap_remove(in_pos);
+                    if let Some((moved, _)) = edges.get(in_pos) {
+                        self.edge_positions[*moved].1 = in_pos;
+                    }
+                }
             }
 
-            if let Some(id) = self.node_to_edges.remove(edge_ends) {
-                self.edge_ends.remove(id);
+            let key = Self::lookup_key(edge_ends);
+            if let Some(ids) = self.node_to_edges.get_mut(&key) {
+                ids.retain(|e| e != &id.get());
+                if ids.is_empty() {
+                    self.node_to_edges.remove(&key);
+                }
             }
         }
-        self.edges.remove(id.get())
+        self.edges
+            .remove(id.get())
+            .ok_or_else(|| self.edge_error(id))
     }
 
     #[inline]
-    fn edge(&self, id: EdgeId<Self>) -> Option<&E> {
-        self.edges.get(id.get())
+    fn edge_mut(&mut self, id: EdgeId<Self>) -> Option<&mut E> {
+        self.edges.get_mut(id.get())
     }
+}
+
+impl<N, E, D: EdgeDirection> Index<NodeId<Self>> for Graph<N, E, D> {
+    type Output = N;
 
     #[inline]
-    fn edges(&self) -> impl Iterator<Item = EdgeId<Self>> {
-        self.edges.keys().map(EdgeId::new)
+    fn index(&self, id: NodeId<Self>) -> &Self::Output {
+        self.nodes
+            .get(id.get())
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
     }
+}
 
+impl<N, E, D: EdgeDirection> IndexMut<NodeId<Self>> for Graph<N, E, D> {
     #[inline]
-    fn edge_between(&self, edge_ends: EdgeEnds<Self>) -> Option<EdgeId<Self>> {
-        self.node_to_edges.get(&edge_ends).copied().map(EdgeId::new)
+    fn index_mut(&mut self, id: NodeId<Self>) -> &mut Self::Output {
+        self.nodes
+            .get_mut(id.get())
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
     }
+}
+
+impl<N, E, D: EdgeDirection> Index<EdgeId<Self>> for Graph<N, E, D> {
+    type Output = E;
 
     #[inline]
-    fn edge_ends(&self, id: EdgeId<Self>) -> Option<&EdgeEnds<Self>> {
-        self.edge_ends.get(id.get())
+    fn index(&self, id: EdgeId<Self>) -> &Self::Output {
+        self.edges
+            .get(id.get())
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
     }
+}
 
+impl<N, E, D: EdgeDirection> IndexMut<EdgeId<Self>> for Graph<N, E, D> {
     #[inline]
-    fn contains_edge(&self, id: EdgeId<Self>) -> bool {
-        self.edges.contains_key(id.get())
+    fn index_mut(&mut self, id: EdgeId<Self>) -> &mut Self::Output {
+        self.edges
+            .get_mut(id.get())
+            .unwrap_or_else(|| panic!("{id:?} isn't part of the graph"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::storage::direction::Undirected;
+
+    // every edge has to be found at its recorded positions, and the lists must not hold more
+    fn assert_positions<N, E, D: EdgeDirection>(graph: &Graph<N, E, D>) {
+        for id in graph.edges() {
+            let EdgeEnds { from, to } = graph.edge_ends[id.get()];
+            let (out_pos, in_pos) = graph.edge_positions[id.get()];
+            assert_eq!(
+                graph.outgoing_edges[from.get()][out_pos],
+                (id.get(), to.get())
+            );
+            assert_eq!(
+                graph.incoming_edges[to.get()][in_pos],
+                (id.get(), from.get())
+            );
+        }
+        assert_eq!(graph.edge_positions.len(), graph.edge_count());
+        let listed = |lists: &SecondaryMap<_, Vec<_>>| lists.values().map(Vec::len).sum::<usize>();
+        assert_eq!(listed(&graph.outgoing_edges), graph.edge_count());
+        assert_eq!(listed(&graph.incoming_edges), graph.edge_count());
     }
 
-    #[inline]
-    fn edge_count(&self) -> usize {
-        self.edges.len()
+    #[test]
+    fn parallel_edges() {
+        let mut graph = Graph::<_, _>::multigraph();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let first = graph.add_edge(a, b, 1).unwrap();
+        let second = graph.add_edge(a, b, 2).unwrap();
+        let third = graph.add_edge(a, b, 3).unwrap();
+        let ends = EdgeEnds { from: a, to: b };
+        assert!(graph.edges_between(ends).eq([first, second, third]));
+        assert!(graph.outcoming_edges(a).eq([first, second, third]));
+        assert!(graph.incoming_edges(b).eq([first, second, third]));
+
+        // removing one of them keeps the others
+        assert_eq!(graph.remove_edge(second), Some(2));
+        assert!(graph.edges_between(ends).eq([first, third]));
+        assert!(graph.outcoming_edges(a).eq([first, third]));
+        assert!(graph.incoming_edges(b).eq([first, third]));
+        assert!(graph.outcoming_nodes(a).eq([b, b]));
+        assert!(graph.has_edge_between(ends));
+
+        graph.remove_edge(first);
+        graph.remove_edge(third);
+        assert!(!graph.has_edge_between(ends));
+        assert_eq!(graph.outcoming_nodes(a).count(), 0);
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn parallel_edges_need_multigraph() {
+        let mut graph = Graph::<_, _>::new();
+        assert!(!graph.allows_parallel_edges());
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let edge = graph.add_edge(a, b, 1).unwrap();
+        assert_eq!(graph.add_edge(a, b, 2), None);
+        // the opposite direction is a different pair of nodes
+        assert!(graph.add_edge(b, a, 3).is_some());
+        assert!(graph.edges_between(EdgeEnds { from: a, to: b }).eq([edge]));
+        assert_eq!(graph.edge_count(), 2);
+    }
+
+    #[test]
+    fn undirected_edges() {
+        let mut graph = Graph::<_, _, Undirected>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let edge = graph.add_edge(a, b, 1).unwrap();
+        // the reversed pair is the same pair of nodes
+        assert_eq!(graph.add_edge(b, a, 2), None);
+        assert_eq!(graph.edge_between(EdgeEnds { from: b, to: a }), Some(edge));
+        assert!(graph.outcoming_nodes(b).eq([a]));
+        assert!(graph.incoming_nodes(a).eq([b]));
+        assert!(graph.neighbor_nodes(a).eq([b]));
+        assert!(graph.incoming_edges(a).eq([edge]));
+
+        let mut graph = Graph::<_, _, Undirected>::multigraph();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let first = graph.add_edge(a, b, 1).unwrap();
+        let second = graph.add_edge(b, a, 2).unwrap();
+        assert!(graph
+            .edges_between(EdgeEnds { from: a, to: b })
+            .eq([first, second]));
+        assert!(graph.neighbor_nodes(a).eq([b, b]));
+    }
+
+    #[test]
+    fn self_loops_are_neighbors_once() {
+        let mut graph = Graph::<_, _, Undirected>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let looped = graph.add_edge(a, a, 1).unwrap();
+        let edge = graph.add_edge(a, b, 2).unwrap();
+        assert!(graph.neighbor_nodes(a).eq([a, b]));
+        assert!(graph.outcoming_edges(a).eq([looped, edge]));
+        assert!(graph.neighbor_nodes(b).eq([a]));
+
+        // directed self loops are incoming and outgoing
+        let mut graph = Graph::<_, _, Directed>::new();
+        let a = graph.add_node("a");
+        graph.add_edge(a, a, 1).unwrap();
+        assert!(graph.incoming_nodes(a).eq([a]));
+        assert!(graph.outcoming_nodes(a).eq([a]));
+    }
+
+    #[test]
+    fn stale_and_missing_ids() {
+        let mut graph = Graph::<_, _>::new();
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        let removed = graph.add_edge(a, b, 1).unwrap();
+        assert_eq!(graph.try_remove_edge(removed), Ok(1));
+        assert_eq!(
+            graph.try_remove_edge(removed),
+            Err(GraphError::StaleEdge(removed))
+        );
+        assert_eq!(graph.try_edge(removed), Err(GraphError::StaleEdge(removed)));
+        assert_eq!(graph.try_remove_node(a), Ok("a"));
+        assert_eq!(graph.try_remove_node(a), Err(GraphError::StaleNode(a)));
+        assert_eq!(graph.try_update_node(a, "c"), Err(GraphError::StaleNode(a)));
+        assert_eq!(graph.try_add_edge(a, b, 2), Err(GraphError::StaleNode(a)));
+
+        // ids of a larger graph point past every slot this one ever used
+        let mut other = Graph::<_, _>::new();
+        let ids = ["a", "b", "c"].map(|node| other.add_node(node));
+        other.add_edge(ids[0], ids[1], 1);
+        let edge = other.add_edge(ids[1], ids[2], 2).unwrap();
+        assert_eq!(
+            graph.try_remove_node(ids[2]),
+            Err(GraphError::MissingNode(ids[2]))
+        );
+        assert_eq!(graph.try_node(ids[2]), Err(GraphError::MissingNode(ids[2])));
+        assert_eq!(
+            graph.try_remove_edge(edge),
+            Err(GraphError::MissingEdge(edge))
+        );
+
+        // slots this graph used as well, but in versions it never handed out
+        other.remove_node(ids[1]);
+        let node = other.add_node("d");
+        other.add_edge(ids[0], ids[2], 3);
+        other.add_edge(ids[2], ids[0], 4);
+        let edge = other
+            .edges()
+            .find(|id| id.slot() == removed.slot())
+            .unwrap();
+        assert_eq!(node.slot(), b.slot());
+        assert_eq!(graph.try_node(node), Err(GraphError::MissingNode(node)));
+        assert_eq!(
+            graph.try_remove_edge(edge),
+            Err(GraphError::MissingEdge(edge))
+        );
+    }
+
+    #[test]
+    fn rejected_edges() {
+        let mut graph = Graph::<_, _>::new().with_self_loops(false);
+        let a = graph.add_node("a");
+        let b = graph.add_node("b");
+        graph.try_add_edge(a, b, 1).unwrap();
+        assert_eq!(
+            graph.try_add_edge(a, b, 2),
+            Err(GraphError::DuplicateEdge(EdgeEnds { from: a, to: b }))
+        );
+        assert_eq!(
+            graph.try_add_edge(a, a, 3),
+            Err(GraphError::SelfLoopRejected(a))
+        );
+        assert_eq!(graph.edge_count(), 1);
+    }
+
+    #[test]
+    fn swap_removal_keeps_positions() {
+        let mut graph = Graph::<_, _>::multigraph();
+        let [hub, a, b, c] = [0, 1, 2, 3].map(|node| graph.add_node(node));
+        let [first, middle, parallel, looped, _, last, _] = [
+            (hub, a),
+            (hub, b),
+            (hub, a),
+            (hub, hub),
+            (c, hub),
+            (hub, c),
+            (b, hub),
+        ]
+        .map(|(from, to)| graph.add_edge(from, to, ()).unwrap());
+        assert_positions(&graph);
+
+        // the last edge of the list takes the place of the removed one
+        graph.remove_edge(middle);
+        assert_positions(&graph);
+        assert_eq!(graph.outgoing_edges[hub.get()][1].0, last.get());
+        graph.remove_edge(last);
+        assert_positions(&graph);
+        assert_eq!(
+            graph.outcoming_edges(hub).collect::<HashSet<_>>(),
+            HashSet::from([first, parallel, looped])
+        );
+
+        // self loops sit in both lists of the same node
+        graph.remove_edge(first);
+        graph.remove_edge(looped);
+        assert_positions(&graph);
+        assert!(graph.outcoming_edges(hub).eq([parallel]));
+
+        graph.remove_node(a);
+        assert_positions(&graph);
+        assert_eq!(graph.outcoming_edges(hub).count(), 0);
+        assert_eq!(graph.incoming_edges(hub).count(), 2);
+        graph.remove_node(hub);
+        assert_positions(&graph);
+        assert_eq!(graph.edge_count(), 0);
+    }
+
+    #[test]
+    fn undirected_swap_removal_keeps_positions() {
+        let mut graph = Graph::<_, _, Undirected>::multigraph();
+        let nodes = (0..4).map(|node| graph.add_node(node)).collect::<Vec<_>>();
+        let edges = [(0, 1), (1, 2), (2, 0), (0, 3), (3, 0), (1, 1), (0, 2)]
+            .map(|(from, to)| graph.add_edge(nodes[from], nodes[to], ()).unwrap());
+        for edge in [edges[0], edges[6], edges[3], edges[5]] {
+            graph.remove_edge(edge);
+            assert_positions(&graph);
+        }
+        assert_eq!(
+            graph.neighbor_edges(nodes[0]).collect::<HashSet<_>>(),
+            HashSet::from([edges[2], edges[4]])
+        );
+        graph.remove_node(nodes[2]);
+        assert_positions(&graph);
+        assert!(graph.neighbor_edges(nodes[0]).eq([edges[4]]));
     }
 }
@@ -1,4 +1,7 @@
+pub mod direction;
 pub mod edge;
+pub mod entry;
+pub mod error;
 pub mod graph;
 pub mod node;
 pub mod traits;
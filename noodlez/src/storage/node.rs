@@ -1,6 +1,9 @@
+use core::hash::Hash;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
+use slotmap::{Key, KeyData};
+
 slotmap::new_key_type! {
     /// Id of node objects
     pub struct InternalNodeID;
@@ -24,6 +27,26 @@ impl<G> NodeId<G> {
     pub(crate) fn get(self) -> InternalNodeID {
         self.id
     }
+
+    // index of the slot the id points to, independent of its version
+    #[inline]
+    pub(crate) fn slot(self) -> u32 {
+        self.id.data().as_ffi() as u32
+    }
+
+    // version of the slot the id was handed out for
+    #[inline]
+    pub(crate) fn version(self) -> u32 {
+        (self.id.data().as_ffi() >> 32) as u32
+    }
+
+    // id for storages which address their elements with dense indices instead of a slotmap
+    #[inline]
+    pub(crate) fn from_slot(slot: u32) -> Self {
+        Self::new(InternalNodeID::from(KeyData::from_ffi(
+            1 << 32 | u64::from(slot),
+        )))
+    }
 }
 
 impl<G> Clone for NodeId<G> {
@@ -47,3 +70,9 @@ impl<G> PartialEq for NodeId<G> {
 }
 
 impl<G> Eq for NodeId<G> {}
+
+impl<G> Hash for NodeId<G> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
@@ -1,28 +1,30 @@
 use super::{
+    direction::EdgeDirection,
     edge::{EdgeEnds, EdgeId},
+    entry::{EdgeEntry, Entry, NodeEntry},
+    error::GraphError,
     node::NodeId,
 };
 
-pub trait GraphStorage<N, E>
+/// Read-only half of a graph storage, consisting of accessors, predicates and properties
+pub trait GraphRead<N, E>
 where
     Self: Sized,
 {
-    // === node stuff ===
-    // operations
-    fn add_node(&mut self, node: N) -> NodeId<Self>;
-    fn update_node(&mut self, id: NodeId<Self>, node: N);
-    fn remove_node(&mut self, id: NodeId<Self>) -> Option<N>;
+    type Direction: EdgeDirection;
+
     #[inline]
-    fn disconnect_node(&mut self, id: NodeId<Self>) {
-        self.neighbor_edges(id)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .for_each(|id| {
-                self.remove_edge(id);
-            });
+    fn is_directed(&self) -> bool {
+        Self::Direction::DIRECTED
     }
+
+    // === node stuff ===
     // accessors
     fn node(&self, id: NodeId<Self>) -> Option<&N>;
+    #[inline]
+    fn try_node(&self, id: NodeId<Self>) -> Result<&N, GraphError<Self>> {
+        self.node(id).ok_or(GraphError::MissingNode(id))
+    }
     fn nodes(&self) -> impl Iterator<Item = NodeId<Self>>;
     fn incoming_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>>;
     fn outcoming_nodes(&self, id: NodeId<Self>) -> impl Iterator<Item = NodeId<Self>>;
@@ -37,14 +39,18 @@ where
     fn node_count(&self) -> usize;
 
     // === edge stuff ===
-    // operations
-    fn add_edge(&mut self, from: NodeId<Self>, to: NodeId<Self>, edge: E) -> Option<EdgeId<Self>>;
-    fn update_edge(&mut self, id: EdgeId<Self>, edge: E);
-    fn remove_edge(&mut self, id: EdgeId<Self>) -> Option<E>;
     // accessors
     fn edge(&self, id: EdgeId<Self>) -> Option<&E>;
+    #[inline]
+    fn try_edge(&self, id: EdgeId<Self>) -> Result<&E, GraphError<Self>> {
+        self.edge(id).ok_or(GraphError::MissingEdge(id))
+    }
     fn edges(&self) -> impl Iterator<Item = EdgeId<Self>>;
     fn edge_between(&self, edge_ends: EdgeEnds<Self>) -> Option<EdgeId<Self>>;
+    #[inline]
+    fn edges_between(&self, edge_ends: EdgeEnds<Self>) -> impl Iterator<Item = EdgeId<Self>> {
+        self.edge_between(edge_ends).into_iter()
+    }
     fn edge_ends(&self, id: EdgeId<Self>) -> Option<&EdgeEnds<Self>>;
     #[inline]
     fn incoming_edges(&self, id: NodeId<Self>) -> impl Iterator<Item = EdgeId<Self>> {
@@ -67,3 +73,90 @@ where
     // properties
     fn edge_count(&self) -> usize;
 }
+
+/// Mutating half of a graph storage
+pub trait GraphWrite<N, E>: GraphRead<N, E> {
+    // === node stuff ===
+    // operations
+    fn add_node(&mut self, node: N) -> NodeId<Self>;
+    #[inline]
+    fn try_add_node(&mut self, node: N) -> Result<NodeId<Self>, GraphError<Self>> {
+        Ok(self.add_node(node))
+    }
+    #[inline]
+    fn update_node(&mut self, id: NodeId<Self>, node: N) {
+        let _ = self.try_update_node(id, node);
+    }
+    /// Replaces the weight of the node and returns the previous one
+    fn try_update_node(&mut self, id: NodeId<Self>, node: N) -> Result<N, GraphError<Self>>;
+    #[inline]
+    fn remove_node(&mut self, id: NodeId<Self>) -> Option<N> {
+        self.try_remove_node(id).ok()
+    }
+    fn try_remove_node(&mut self, id: NodeId<Self>) -> Result<N, GraphError<Self>>;
+    #[inline]
+    fn disconnect_node(&mut self, id: NodeId<Self>) {
+        self.neighbor_edges(id)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .for_each(|id| {
+                self.remove_edge(id);
+            });
+    }
+    #[inline]
+    fn try_disconnect_node(&mut self, id: NodeId<Self>) -> Result<(), GraphError<Self>> {
+        self.try_node(id)?;
+        self.disconnect_node(id);
+        Ok(())
+    }
+    // accessors
+    fn node_mut(&mut self, id: NodeId<Self>) -> Option<&mut N>;
+    #[inline]
+    fn node_entry(&mut self, id: NodeId<Self>) -> NodeEntry<'_, Self, N> {
+        match self.node_mut(id) {
+            Some(node) => Entry::Occupied(id, node),
+            None => Entry::Vacant(id),
+        }
+    }
+
+    // === edge stuff ===
+    // operations
+    #[inline]
+    fn add_edge(&mut self, from: NodeId<Self>, to: NodeId<Self>, edge: E) -> Option<EdgeId<Self>> {
+        self.try_add_edge(from, to, edge).ok()
+    }
+    fn try_add_edge(
+        &mut self,
+        from: NodeId<Self>,
+        to: NodeId<Self>,
+        edge: E,
+    ) -> Result<EdgeId<Self>, GraphError<Self>>;
+    #[inline]
+    fn update_edge(&mut self, id: EdgeId<Self>, edge: E) {
+        let _ = self.try_update_edge(id, edge);
+    }
+    /// Replaces the weight of the edge and returns the previous one
+    fn try_update_edge(&mut self, id: EdgeId<Self>, edge: E) -> Result<E, GraphError<Self>>;
+    #[inline]
+    fn remove_edge(&mut self, id: EdgeId<Self>) -> Option<E> {
+        self.try_remove_edge(id).ok()
+    }
+    fn try_remove_edge(&mut self, id: EdgeId<Self>) -> Result<E, GraphError<Self>>;
+    // accessors
+    fn edge_mut(&mut self, id: EdgeId<Self>) -> Option<&mut E>;
+    #[inline]
+    fn edge_entry(&mut self, id: EdgeId<Self>) -> EdgeEntry<'_, Self, E> {
+        match self.edge_mut(id) {
+            Some(edge) => Entry::Occupied(id, edge),
+            None => Entry::Vacant(id),
+        }
+    }
+}
+
+/// Full featured graph storage which can be queried and mutated
+///
+/// This is only a shorthand bound: the methods live on [`GraphRead`] and [`GraphWrite`], so those
+/// traits have to be in scope to call them. Importing `noodlez::prelude::*` brings in all three.
+pub trait GraphStorage<N, E>: GraphWrite<N, E> {}
+
+impl<N, E, G: GraphWrite<N, E>> GraphStorage<N, E> for G {}
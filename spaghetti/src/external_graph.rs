@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use noodlez::storage::{
-    edge::EdgeId, graph::slotmap_graph::Graph, node::NodeId, traits::GraphStorage,
+    edge::EdgeId, graph::slotmap_graph::Graph, node::NodeId, traits::GraphWrite,
 };
 
 use crate::bevy_graph::{
@@ -19,9 +19,15 @@ impl Plugin for ExternalGraphPlugin {
 
 type GraphType = Graph<Vec2, ()>;
 
-#[derive(Debug, Resource, Deref, DerefMut, Default)]
+#[derive(Debug, Resource, Deref, DerefMut)]
 pub struct BevyGraph(GraphType);
 
+impl Default for BevyGraph {
+    fn default() -> Self {
+        Self(GraphType::multigraph())
+    }
+}
+
 #[derive(Debug, Clone, Copy, Component)]
 pub struct BevyNodeId(NodeId<GraphType>);
 